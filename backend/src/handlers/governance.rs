@@ -1,7 +1,10 @@
 use axum::{extract::Path, Json};
 use uuid::Uuid;
 
-use crate::models::{GovernanceProposalRequest, GovernanceProposalResponse, VoteRequest, ModelConfigRequest};
+use crate::models::{
+    GovernanceProposalRequest, GovernanceProposalResponse, VoteRequest, ModelConfigRequest,
+    SignedVoteRequest, RelaySignedVotesRequest, RelaySignedVotesResponse,
+};
 use crate::services::governance_service::GovernanceService;
 
 pub async fn create_proposal(
@@ -50,6 +53,30 @@ pub async fn vote(
     Ok(Json(serde_json::json!({"status": "success"})))
 }
 
+pub async fn submit_signed_vote(
+    Path(proposal_id): Path<u64>,
+    Json(payload): Json<SignedVoteRequest>,
+) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
+    let governance_service = GovernanceService::new();
+    let pending = governance_service.submit_signed_vote(proposal_id, payload)
+        .await
+        .map_err(|_| axum::http::StatusCode::BAD_REQUEST)?;
+    
+    Ok(Json(serde_json::json!({"status": "collected", "pending": pending})))
+}
+
+pub async fn relay_signed_votes(
+    Path(proposal_id): Path<u64>,
+    Json(payload): Json<RelaySignedVotesRequest>,
+) -> Result<Json<RelaySignedVotesResponse>, axum::http::StatusCode> {
+    let governance_service = GovernanceService::new();
+    let result = governance_service.relay_signed_votes(proposal_id, payload)
+        .await
+        .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
+    
+    Ok(Json(result))
+}
+
 pub async fn execute_proposal(
     Path(proposal_id): Path<u64>,
 ) -> Result<Json<serde_json::Value>, axum::http::StatusCode> {
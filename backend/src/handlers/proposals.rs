@@ -2,7 +2,7 @@ use axum::{extract::{Path, Query}, Json};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use crate::models::{ProposalSubmit, ProposalResponse};
+use crate::models::{ProposalSubmit, ProposalResponse, ProposalRevisionResponse};
 use crate::services::{ipfs_service::IPFSService, solana_service::SolanaService};
 
 #[derive(Deserialize)]
@@ -38,12 +38,14 @@ pub async fn submit_proposal(
         .await
         .map_err(|_| axum::http::StatusCode::INTERNAL_SERVER_ERROR)?;
     
+    let timestamp = chrono::Utc::now().timestamp();
     Ok(Json(ProposalResponse {
         proposal_id,
-        ipfs_hash,
+        ipfs_hash: ipfs_hash.clone(),
         submitter: String::new(), // 从Solana交易中获取
-        timestamp: chrono::Utc::now().timestamp(),
+        timestamp,
         status: "submitted".to_string(),
+        revisions: vec![ProposalRevisionResponse { revision: 0, ipfs_hash, timestamp }],
     }))
 }
 
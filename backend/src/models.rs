@@ -15,6 +15,14 @@ pub struct ProposalResponse {
     pub submitter: String,
     pub timestamp: i64,
     pub status: String,
+    pub revisions: Vec<ProposalRevisionResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProposalRevisionResponse {
+    pub revision: u16,
+    pub ipfs_hash: String,
+    pub timestamp: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -84,7 +92,28 @@ pub struct GovernanceProposalResponse {
 pub struct VoteRequest {
     pub proposal_id: u64,
     pub vote_type: String, // "for", "against", "abstain"
-    pub voting_power: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedVoteRequest {
+    pub proposal: String,  // proposal account address (base58)
+    pub voter: String,     // voter wallet (base58)
+    pub vote_type: String, // "for", "against", "abstain"
+    pub voting_power: u64, // snapshot voting power the voter signed
+    pub signature: String, // ed25519 signature over the canonical vote message (base58)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelaySignedVotesRequest {
+    pub proposal: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RelaySignedVotesResponse {
+    pub transactions: Vec<String>,
+    pub relayed: usize,
+    pub rejected: usize,
+    pub pending: usize,
 }
 
 // Reward models
@@ -11,6 +11,8 @@ pub fn router() -> Router {
         .route("/proposals", get(governance::get_proposals))
         .route("/proposals/:id", get(governance::get_proposal))
         .route("/proposals/:id/vote", post(governance::vote))
+        .route("/proposals/:id/signed-votes", post(governance::submit_signed_vote))
+        .route("/proposals/:id/signed-votes/relay", post(governance::relay_signed_votes))
         .route("/proposals/:id/execute", post(governance::execute_proposal))
         .route("/config", get(governance::get_model_config))
 }
@@ -1,13 +1,108 @@
-use anyhow::Result;
-use crate::models::{GovernanceProposalRequest, GovernanceProposalResponse, VoteRequest, ModelConfigRequest};
+use anyhow::{anyhow, Result};
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair, Signature, Signer},
+    sysvar,
+    transaction::Transaction,
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use tracing::{info, warn};
+
+use crate::models::{
+    GovernanceProposalRequest, GovernanceProposalResponse, VoteRequest, ModelConfigRequest,
+    SignedVoteRequest, RelaySignedVotesRequest, RelaySignedVotesResponse,
+};
+
+// 与链上程序 state::governance 中的种子和签名域保持一致
+const MODEL_CONFIG_SEED: &[u8] = b"model-config";
+const PROGRAM_STATE_SEED: &[u8] = b"program-state";
+const VOTER_RECORD_SEED: &[u8] = b"voter-record";
+const DELEGATION_SEED: &[u8] = b"delegation";
+const SIGNED_VOTE_DOMAIN: &[u8] = b"daollm-signed-vote";
+
+// 单笔交易能容纳的签名投票数（受1232字节交易大小限制）
+const MAX_SIGNED_VOTES_PER_BATCH: usize = 3;
+// 待中继签名投票池：每个提案一个Redis哈希，字段为投票者地址
+const SIGNED_VOTE_POOL_PREFIX: &str = "governance:signed-votes";
+
+#[derive(Clone)]
+struct PendingSignedVote {
+    voter: Pubkey,
+    proposal_id: u64,
+    message: Vec<u8>,
+    signature: Signature,
+}
+
+/// Stored form of a pending vote; the voter is the hash field.
+#[derive(Serialize, Deserialize)]
+struct StoredSignedVote {
+    proposal_id: u64,
+    message: Vec<u8>,
+    signature: String,
+}
+
+impl PendingSignedVote {
+    fn to_stored(&self) -> Result<String> {
+        Ok(serde_json::to_string(&StoredSignedVote {
+            proposal_id: self.proposal_id,
+            message: self.message.clone(),
+            signature: self.signature.to_string(),
+        })?)
+    }
+
+    fn from_stored(voter: &str, stored: &str) -> Result<Self> {
+        let stored: StoredSignedVote = serde_json::from_str(stored)?;
+        Ok(Self {
+            voter: Pubkey::from_str(voter)?,
+            proposal_id: stored.proposal_id,
+            message: stored.message,
+            signature: Signature::from_str(&stored.signature)?,
+        })
+    }
+}
+
+fn signed_vote_pool_key(proposal: &Pubkey) -> String {
+    format!("{}:{}", SIGNED_VOTE_POOL_PREFIX, proposal)
+}
+
+// read_keypair_file does not expand `~`, which env.example uses for the relayer key
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home.trim_end_matches('/'), rest),
+        _ => path.to_string(),
+    }
+}
 
 pub struct GovernanceService {
-    // TODO: Add Solana client and program instance
+    rpc_url: String,
+    redis_url: String,
+    program_id: Pubkey,
 }
 
 impl GovernanceService {
     pub fn new() -> Self {
-        Self {}
+        let rpc_url = std::env::var("SOLANA_RPC_URL")
+            .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
+        let redis_url = std::env::var("REDIS_URL")
+            .unwrap_or_else(|_| "redis://localhost:6379".to_string());
+        
+        let program_id = Pubkey::from_str(
+            &std::env::var("PROGRAM_ID")
+                .unwrap_or_else(|_| "Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS".to_string())
+        ).unwrap();
+        
+        Self {
+            rpc_url,
+            redis_url,
+            program_id,
+        }
     }
     
     pub async fn create_proposal(&self, request: GovernanceProposalRequest) -> Result<GovernanceProposalResponse> {
@@ -47,15 +142,348 @@ impl GovernanceService {
     }
     
     pub async fn get_model_config(&self) -> Result<ModelConfigRequest> {
-        // TODO: Query Solana for current model config
-        Ok(ModelConfigRequest {
-            model_version: 1,
-            learning_rate: 0.001,
-            batch_size: 32,
-            max_epochs: 100,
-            inference_timeout: 30,
-            min_node_reputation: 50,
+        let (address, _) = Pubkey::find_program_address(&[MODEL_CONFIG_SEED], &self.program_id);
+        let client = RpcClient::new(self.rpc_url.clone());
+        let data = client.get_account_data(&address).await?;
+        parse_model_config(&data)
+    }
+    
+    /// Verifies a voter's signature over the canonical vote message and queues it
+    /// for the relayer in Redis, so pending votes survive a backend restart.
+    /// Re-signing before the batch is relayed replaces the earlier vote. Returns
+    /// the number of votes pending for the proposal.
+    pub async fn submit_signed_vote(&self, proposal_id: u64, request: SignedVoteRequest) -> Result<usize> {
+        let proposal = Pubkey::from_str(&request.proposal)?;
+        let voter = Pubkey::from_str(&request.voter)?;
+        let signature = Signature::from_str(&request.signature)?;
+        let message = signed_vote_message(&proposal, proposal_id, &request.vote_type, request.voting_power)?;
+        if !signature.verify(voter.as_ref(), &message) {
+            return Err(anyhow!("Invalid vote signature"));
+        }
+        
+        let vote = PendingSignedVote { voter, proposal_id, message, signature };
+        let key = signed_vote_pool_key(&proposal);
+        let mut pool = self.signed_vote_pool().await?;
+        let _: () = pool.hset(&key, voter.to_string(), vote.to_stored()?).await?;
+        let pending: usize = pool.hlen(&key).await?;
+        Ok(pending)
+    }
+    
+    /// Submits the next batch of pending signed votes in one transaction: an ed25519
+    /// program instruction carrying the signatures followed by `submit_signed_votes`.
+    /// If the batch is rejected each vote is retried alone so one bad signature
+    /// cannot block the rest; votes the program still rejects are dropped.
+    pub async fn relay_signed_votes(
+        &self,
+        proposal_id: u64,
+        request: RelaySignedVotesRequest,
+    ) -> Result<RelaySignedVotesResponse> {
+        let proposal = Pubkey::from_str(&request.proposal)?;
+        let key = signed_vote_pool_key(&proposal);
+        let mut pool = self.signed_vote_pool().await?;
+        let stored: HashMap<String, String> = pool.hgetall(&key).await?;
+        let mut batch = Vec::new();
+        for (voter, vote) in &stored {
+            match PendingSignedVote::from_stored(voter, vote) {
+                Ok(vote) if vote.proposal_id == proposal_id => batch.push(vote),
+                Ok(_) => {}
+                Err(e) => warn!("Skipping unreadable signed vote from {}: {}", voter, e),
+            }
+            if batch.len() == MAX_SIGNED_VOTES_PER_BATCH {
+                break;
+            }
+        }
+        if batch.is_empty() {
+            return Err(anyhow!("No signed votes pending for proposal {}", proposal_id));
+        }
+        
+        let keypair_path = std::env::var("RELAYER_KEYPAIR")
+            .map_err(|_| anyhow!("RELAYER_KEYPAIR is not configured"))?;
+        let relayer = read_keypair_file(expand_home(&keypair_path))
+            .map_err(|e| anyhow!("Failed to read relayer keypair {}: {}", keypair_path, e))?;
+        let client = RpcClient::new(self.rpc_url.clone());
+        
+        let mut transactions = Vec::new();
+        let mut rejected = 0;
+        match self.send_signed_votes(&client, &relayer, &proposal, proposal_id, &batch).await {
+            Ok(signature) => transactions.push(signature.to_string()),
+            Err(e) => {
+                warn!("Signed vote batch for proposal {} failed, retrying individually: {}", proposal_id, e);
+                for vote in &batch {
+                    let single = std::slice::from_ref(vote);
+                    match self.send_signed_votes(&client, &relayer, &proposal, proposal_id, single).await {
+                        Ok(signature) => transactions.push(signature.to_string()),
+                        Err(e) => {
+                            warn!("Dropping signed vote from {}: {}", vote.voter, e);
+                            rejected += 1;
+                        }
+                    }
+                }
+            }
+        }
+        
+        // 只移除已处理的投票；中继期间重新签名的投票保留在池中
+        for vote in &batch {
+            let field = vote.voter.to_string();
+            let current: Option<String> = pool.hget(&key, &field).await?;
+            if current.as_deref() == Some(vote.to_stored()?.as_str()) {
+                let _: () = pool.hdel(&key, &field).await?;
+            }
+        }
+        let pending: usize = pool.hlen(&key).await?;
+        info!("Relayed {} signed votes on proposal {}", batch.len() - rejected, proposal_id);
+        
+        Ok(RelaySignedVotesResponse {
+            transactions,
+            relayed: batch.len() - rejected,
+            rejected,
+            pending,
         })
     }
+    
+    async fn signed_vote_pool(&self) -> Result<redis::aio::MultiplexedConnection> {
+        let client = redis::Client::open(self.redis_url.as_str())?;
+        client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| anyhow!("Signed vote pool unavailable: {}", e))
+    }
+    
+    async fn send_signed_votes(
+        &self,
+        client: &RpcClient,
+        relayer: &Keypair,
+        proposal: &Pubkey,
+        proposal_id: u64,
+        votes: &[PendingSignedVote],
+    ) -> Result<Signature> {
+        let (program_state, _) = Pubkey::find_program_address(&[PROGRAM_STATE_SEED], &self.program_id);
+        let mut accounts = vec![
+            AccountMeta::new_readonly(relayer.pubkey(), true),
+            AccountMeta::new(*proposal, false),
+            AccountMeta::new_readonly(program_state, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ];
+        for vote in votes {
+            let (voter_record, _) =
+                Pubkey::find_program_address(&[VOTER_RECORD_SEED, vote.voter.as_ref()], &self.program_id);
+            let (delegation, _) =
+                Pubkey::find_program_address(&[DELEGATION_SEED, vote.voter.as_ref()], &self.program_id);
+            let vote_address = self.vote_address(&vote.voter, proposal_id);
+            // 委托账户布局：discriminator(8) + delegator(32) + delegate(32)；
+            // 未委托时程序不会读取该位置，用本人投票PDA占位
+            let delegate_vote = match client.get_account_data(&delegation).await {
+                Ok(data) if data.len() >= 72 => self.vote_address(&Pubkey::try_from(&data[40..72])?, proposal_id),
+                _ => vote_address,
+            };
+            accounts.extend([
+                AccountMeta::new(voter_record, false),
+                AccountMeta::new_readonly(vote_address, false),
+                AccountMeta::new_readonly(delegation, false),
+                AccountMeta::new(delegate_vote, false),
+            ]);
+        }
+        
+        let entries: Vec<(&[u8], &[u8], &[u8])> = votes
+            .iter()
+            .map(|vote| (vote.voter.as_ref(), vote.signature.as_ref(), vote.message.as_slice()))
+            .collect();
+        let mut data = anchor_discriminator("submit_signed_votes").to_vec();
+        data.extend_from_slice(&proposal_id.to_le_bytes());
+        let instructions = [
+            Instruction::new_with_bytes(ed25519_program::id(), &ed25519_instruction_data(&entries), vec![]),
+            Instruction::new_with_bytes(self.program_id, &data, accounts),
+        ];
+        
+        let blockhash = client.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &instructions,
+            Some(&relayer.pubkey()),
+            &[relayer],
+            blockhash,
+        );
+        Ok(client.send_and_confirm_transaction(&transaction).await?)
+    }
+    
+    fn vote_address(&self, voter: &Pubkey, proposal_id: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"vote", voter.as_ref(), proposal_id.to_le_bytes().as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+}
+
+/// Canonical message a voter signs: the signing domain followed by the Borsh
+/// encoding of the program's `SignedVoteMessage` (proposal, proposal_id,
+/// vote_type, voting_power).
+pub fn signed_vote_message(
+    proposal: &Pubkey,
+    proposal_id: u64,
+    vote_type: &str,
+    voting_power: u64,
+) -> Result<Vec<u8>> {
+    let vote_type: u8 = match vote_type {
+        "for" => 0,
+        "against" => 1,
+        "abstain" => 2,
+        other => return Err(anyhow!("Unknown vote type: {}", other)),
+    };
+    
+    let mut message = SIGNED_VOTE_DOMAIN.to_vec();
+    message.extend_from_slice(proposal.as_ref());
+    message.extend_from_slice(&proposal_id.to_le_bytes());
+    message.push(vote_type);
+    message.extend_from_slice(&voting_power.to_le_bytes());
+    Ok(message)
+}
+
+/// Builds ed25519 program instruction data verifying every (public key, signature,
+/// message) entry. All offsets point into the instruction itself, which is the
+/// only layout `submit_signed_votes` accepts.
+fn ed25519_instruction_data(entries: &[(&[u8], &[u8], &[u8])]) -> Vec<u8> {
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+    
+    let payload_start = OFFSETS_START + entries.len() * OFFSETS_SIZE;
+    let mut data = vec![entries.len() as u8, 0];
+    let mut payload = Vec::new();
+    for (public_key, signature, message) in entries {
+        let public_key_offset = payload_start + payload.len();
+        payload.extend_from_slice(public_key);
+        let signature_offset = payload_start + payload.len();
+        payload.extend_from_slice(signature);
+        let message_offset = payload_start + payload.len();
+        payload.extend_from_slice(message);
+        
+        for value in [
+            signature_offset as u16,
+            CURRENT_INSTRUCTION,
+            public_key_offset as u16,
+            CURRENT_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    data.extend_from_slice(&payload);
+    data
+}
+
+fn anchor_discriminator(instruction: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("global:{}", instruction).as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+/// Decodes a `ModelConfig` account: 8-byte Anchor discriminator followed by
+/// Borsh-encoded fields.
+fn parse_model_config(data: &[u8]) -> Result<ModelConfigRequest> {
+    fn take<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N]> {
+        if buf.len() < N {
+            return Err(anyhow!("Model config account data too short"));
+        }
+        let (head, rest) = buf.split_at(N);
+        *buf = rest;
+        Ok(head.try_into()?)
+    }
+    
+    let mut buf = data;
+    take::<8>(&mut buf)?; // discriminator
+    Ok(ModelConfigRequest {
+        model_version: u64::from_le_bytes(take(&mut buf)?),
+        learning_rate: f64::from_le_bytes(take(&mut buf)?),
+        batch_size: u32::from_le_bytes(take(&mut buf)?),
+        max_epochs: u32::from_le_bytes(take(&mut buf)?),
+        inference_timeout: u64::from_le_bytes(take(&mut buf)?),
+        min_node_reputation: u8::from_le_bytes(take(&mut buf)?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_model_config() {
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(&3u64.to_le_bytes());
+        data.extend_from_slice(&0.001f64.to_le_bytes());
+        data.extend_from_slice(&32u32.to_le_bytes());
+        data.extend_from_slice(&100u32.to_le_bytes());
+        data.extend_from_slice(&30u64.to_le_bytes());
+        data.push(50);
+        data.extend_from_slice(&0i64.to_le_bytes());
+        data.extend_from_slice(&[0u8; 32]);
+
+        let config = parse_model_config(&data).unwrap();
+        assert_eq!(config.model_version, 3);
+        assert_eq!(config.learning_rate, 0.001);
+        assert_eq!(config.batch_size, 32);
+        assert_eq!(config.max_epochs, 100);
+        assert_eq!(config.inference_timeout, 30);
+        assert_eq!(config.min_node_reputation, 50);
+    }
+
+    #[test]
+    fn test_parse_model_config_rejects_short_data() {
+        assert!(parse_model_config(&[0u8; 16]).is_err());
+    }
+
+    #[test]
+    fn test_signed_vote_message_layout() {
+        let proposal = Pubkey::new_from_array([7u8; 32]);
+        let message = signed_vote_message(&proposal, 42, "against", 1_000).unwrap();
+
+        assert_eq!(message.len(), SIGNED_VOTE_DOMAIN.len() + 32 + 8 + 1 + 8);
+        assert!(message.starts_with(SIGNED_VOTE_DOMAIN));
+        let body = &message[SIGNED_VOTE_DOMAIN.len()..];
+        assert_eq!(&body[..32], &[7u8; 32]);
+        assert_eq!(&body[32..40], &42u64.to_le_bytes());
+        assert_eq!(body[40], 1);
+        assert_eq!(&body[41..], &1_000u64.to_le_bytes());
+
+        assert!(signed_vote_message(&proposal, 42, "maybe", 1_000).is_err());
+    }
+
+    #[test]
+    fn test_pending_signed_vote_roundtrip() {
+        let voter = Pubkey::new_from_array([5u8; 32]);
+        let vote = PendingSignedVote {
+            voter,
+            proposal_id: 9,
+            message: vec![1, 2, 3],
+            signature: Signature::from([4u8; 64]),
+        };
+
+        let restored = PendingSignedVote::from_stored(&voter.to_string(), &vote.to_stored().unwrap()).unwrap();
+        assert_eq!(restored.voter, voter);
+        assert_eq!(restored.proposal_id, 9);
+        assert_eq!(restored.message, vec![1, 2, 3]);
+        assert_eq!(restored.signature, vote.signature);
+        assert_eq!(signed_vote_pool_key(&voter), format!("governance:signed-votes:{}", voter));
+    }
+
+    #[test]
+    fn test_ed25519_instruction_data_offsets() {
+        let (key, signature, message) = ([1u8; 32], [2u8; 64], [3u8; 5]);
+        let data = ed25519_instruction_data(&[(&key, &signature, &message), (&key, &signature, &message)]);
+
+        assert_eq!(data[0], 2);
+        let read = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+        for index in 0..2 {
+            let offsets = 2 + index * 14;
+            assert_eq!(&data[read(offsets + 4)..read(offsets + 4) + 32], &key);
+            assert_eq!(&data[read(offsets)..read(offsets) + 64], &signature);
+            assert_eq!(&data[read(offsets + 8)..read(offsets + 8) + read(offsets + 10)], &message);
+            for at in [offsets + 2, offsets + 6, offsets + 12] {
+                assert_eq!(read(at), u16::MAX as usize);
+            }
+        }
+    }
 }
 
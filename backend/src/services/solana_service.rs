@@ -1,8 +1,11 @@
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use solana_client::nonblocking::rpc_client::RpcClient;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
-use crate::models::ProposalResponse;
+use crate::models::{ProposalResponse, ProposalRevisionResponse};
+
+const PROPOSAL_SEED: &[u8] = b"proposal";
 
 pub struct SolanaService {
     rpc_url: String,
@@ -48,14 +51,127 @@ impl SolanaService {
     }
     
     pub async fn get_proposal(&self, proposal_id: &str) -> Result<Option<ProposalResponse>> {
-        // TODO: 从Solana链上查询单个提案
-        // 这里需要：
-        // 1. 计算提案PDA
-        // 2. 查询账户数据
-        // 3. 解析并返回
-        
-        // 简化版本：返回None
-        Ok(None)
+        let (address, _) = Pubkey::find_program_address(
+            &[PROPOSAL_SEED, proposal_id.as_bytes()],
+            &self.program_id,
+        );
+        let client = RpcClient::new(self.rpc_url.clone());
+        let account = client
+            .get_account_with_commitment(&address, client.commitment())
+            .await?
+            .value;
+        account.map(|account| parse_proposal(&account.data)).transpose()
+    }
+}
+
+/// Borsh reader over account data.
+struct AccountReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> AccountReader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N]> {
+        if self.buf.len() < N {
+            return Err(anyhow!("Proposal account data too short"));
+        }
+        let (head, rest) = self.buf.split_at(N);
+        self.buf = rest;
+        Ok(head.try_into()?)
+    }
+    
+    fn string(&mut self) -> Result<String> {
+        let len = u32::from_le_bytes(self.take()?) as usize;
+        if self.buf.len() < len {
+            return Err(anyhow!("Proposal account data too short"));
+        }
+        let (head, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(String::from_utf8(head.to_vec())?)
+    }
+}
+
+/// Decodes a `Proposal` account, including its revision history. Revision 0 is
+/// the original submission.
+fn parse_proposal(data: &[u8]) -> Result<ProposalResponse> {
+    let mut reader = AccountReader { buf: data };
+    reader.take::<8>()?; // discriminator
+    let submitter = Pubkey::new_from_array(reader.take()?);
+    let proposal_id = reader.string()?;
+    let ipfs_hash = reader.string()?;
+    let timestamp = i64::from_le_bytes(reader.take()?);
+    let status = match reader.take::<1>()?[0] {
+        0 => "submitted",
+        1 => "analyzing",
+        2 => "completed",
+        other => return Err(anyhow!("Unknown proposal status {}", other)),
+    };
+    if reader.take::<1>()?[0] == 1 {
+        reader.take::<32>()?; // analysis_task
+    }
+    reader.take::<2>()?; // inference_count
+    reader.string()?; // consensus_hash
+    reader.take::<32>()?; // contributors_root
+    reader.take::<2>()?; // agreement_bps
+    
+    let count = u32::from_le_bytes(reader.take()?);
+    let mut revisions = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        revisions.push(ProposalRevisionResponse {
+            revision: u16::from_le_bytes(reader.take()?),
+            ipfs_hash: reader.string()?,
+            timestamp: i64::from_le_bytes(reader.take()?),
+        });
+    }
+    
+    Ok(ProposalResponse {
+        proposal_id,
+        ipfs_hash,
+        submitter: submitter.to_string(),
+        timestamp,
+        status: status.to_string(),
+        revisions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_string(data: &mut Vec<u8>, value: &str) {
+        data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        data.extend_from_slice(value.as_bytes());
+    }
+
+    #[test]
+    fn test_parse_proposal_revisions() {
+        let mut data = vec![0u8; 8];
+        data.extend_from_slice(&[5u8; 32]);
+        push_string(&mut data, "proposal-1");
+        push_string(&mut data, "QmAmended");
+        data.extend_from_slice(&100i64.to_le_bytes());
+        data.push(0);
+        data.push(0);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        push_string(&mut data, "");
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        for (revision, hash, ts) in [(0u16, "QmOriginal", 100i64), (1, "QmAmended", 160)] {
+            data.extend_from_slice(&revision.to_le_bytes());
+            push_string(&mut data, hash);
+            data.extend_from_slice(&ts.to_le_bytes());
+        }
+
+        let proposal = parse_proposal(&data).unwrap();
+        assert_eq!(proposal.proposal_id, "proposal-1");
+        assert_eq!(proposal.ipfs_hash, "QmAmended");
+        assert_eq!(proposal.status, "submitted");
+        assert_eq!(proposal.revisions.len(), 2);
+        assert_eq!(proposal.revisions[0].ipfs_hash, "QmOriginal");
+        assert_eq!(proposal.revisions[1].revision, 1);
+        assert_eq!(proposal.revisions[1].timestamp, 160);
+
+        assert!(parse_proposal(&data[..data.len() - 4]).is_err());
     }
 }
 
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::governance::GOVERNANCE_CONFIG_SEED,
+    state::{
+        Council, CouncilAction, CouncilActionKind, CouncilApproval, GovernanceConfig,
+        COUNCIL_ACTION_SEED, COUNCIL_SEED, GOVERNANCE_AUTHORITY_SEED, MAX_COUNCIL_MEMBERS,
+    },
+    ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct InitializeCouncil<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Council::MAX_SIZE,
+        seeds = [COUNCIL_SEED],
+        bump
+    )]
+    pub council: Account<'info, Council>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_council(
+    ctx: Context<InitializeCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_council(&members, threshold)?;
+
+    let council = &mut ctx.accounts.council;
+    council.members = members;
+    council.threshold = threshold;
+    council.rotated_at = Clock::get()?.unix_timestamp;
+    council.bump = ctx.bumps.council;
+
+    msg!(
+        "Council initialized with {} members, threshold {}",
+        council.members.len(),
+        council.threshold
+    );
+    Ok(())
+}
+
+/// Rotation is only reachable through a governance proposal, which signs as the
+/// governance PDA.
+#[derive(Accounts)]
+pub struct SetCouncilMembers<'info> {
+    #[account(seeds = [GOVERNANCE_AUTHORITY_SEED], bump)]
+    pub governance_authority: Signer<'info>,
+    #[account(mut, seeds = [COUNCIL_SEED], bump = council.bump)]
+    pub council: Account<'info, Council>,
+}
+
+pub fn set_council_members(
+    ctx: Context<SetCouncilMembers>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    validate_council(&members, threshold)?;
+
+    let council = &mut ctx.accounts.council;
+    council.members = members;
+    council.threshold = threshold;
+    council.rotated_at = Clock::get()?.unix_timestamp;
+
+    msg!(
+        "Council rotated to {} members, threshold {}",
+        council.members.len(),
+        council.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct ProposeCouncilAction<'info> {
+    #[account(mut)]
+    pub member: Signer<'info>,
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        constraint = council.is_member(&member.key()) @ ErrorCode::NotCouncilMember
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        init,
+        payer = member,
+        space = 8 + CouncilAction::MAX_SIZE,
+        seeds = [COUNCIL_ACTION_SEED, &action_id.to_le_bytes()],
+        bump
+    )]
+    pub council_action: Account<'info, CouncilAction>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn propose_council_action(
+    ctx: Context<ProposeCouncilAction>,
+    action_id: u64,
+    action: CouncilActionKind,
+) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let clock = Clock::get()?;
+    let council_action = &mut ctx.accounts.council_action;
+
+    council_action.action_id = action_id;
+    council_action.proposer = member;
+    council_action.action = action;
+    // 发起人的提议即计为一票批准
+    council_action.approvals = vec![CouncilApproval {
+        member,
+        approved_at: clock.unix_timestamp,
+    }];
+    council_action.created_at = clock.unix_timestamp;
+    council_action.executed_at = 0;
+    council_action.bump = ctx.bumps.council_action;
+
+    msg!("Council action {} proposed by {}", action_id, member);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(action_id: u64)]
+pub struct ApproveCouncilAction<'info> {
+    pub member: Signer<'info>,
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        constraint = council.is_member(&member.key()) @ ErrorCode::NotCouncilMember
+    )]
+    pub council: Account<'info, Council>,
+    #[account(
+        mut,
+        seeds = [COUNCIL_ACTION_SEED, &action_id.to_le_bytes()],
+        bump = council_action.bump,
+        constraint = council_action.executed_at == 0 @ ErrorCode::CouncilActionExecuted
+    )]
+    pub council_action: Account<'info, CouncilAction>,
+}
+
+pub fn approve_council_action(ctx: Context<ApproveCouncilAction>, action_id: u64) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let council_action = &mut ctx.accounts.council_action;
+
+    require!(
+        !council_action
+            .approvals
+            .iter()
+            .any(|approval| approval.member == member),
+        ErrorCode::CouncilActionAlreadyApproved
+    );
+    require!(
+        council_action.approvals.len() < MAX_COUNCIL_MEMBERS,
+        ErrorCode::InvalidCouncil
+    );
+
+    council_action.approvals.push(CouncilApproval {
+        member,
+        approved_at: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Council action {} approved by {} ({} approvals)",
+        action_id,
+        member,
+        council_action.approvals.len()
+    );
+    Ok(())
+}
+
+/// Checks that `council_action` authorizes exactly `expected`, carries a quorum of
+/// approvals from current members, and marks it executed.
+pub(crate) fn consume_council_action(
+    council: &Council,
+    council_action: &mut CouncilAction,
+    expected: CouncilActionKind,
+    executor: &Pubkey,
+) -> Result<()> {
+    require!(council.is_member(executor), ErrorCode::NotCouncilMember);
+    // 已离任成员的批准不计入法定数
+    require!(council_action.executed_at == 0, ErrorCode::CouncilActionExecuted);
+    require!(council_action.action == expected, ErrorCode::CouncilActionMismatch);
+    require!(
+        council_action.approvals_from(council) >= council.threshold as usize,
+        ErrorCode::InsufficientCouncilApprovals
+    );
+
+    council_action.executed_at = Clock::get()?.unix_timestamp;
+
+    msg!("Council action {} executed by {}", council_action.action_id, executor);
+    Ok(())
+}
+
+fn validate_council(members: &[Pubkey], threshold: u8) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_COUNCIL_MEMBERS,
+        ErrorCode::InvalidCouncil
+    );
+    require!(
+        threshold > 0 && threshold as usize <= members.len(),
+        ErrorCode::InvalidCouncil
+    );
+    // 成员不可重复，否则一人即可凑足多票
+    for (index, member) in members.iter().enumerate() {
+        require!(!members[..index].contains(member), ErrorCode::InvalidCouncil);
+    }
+    Ok(())
+}
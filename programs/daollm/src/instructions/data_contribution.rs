@@ -1,8 +1,15 @@
-use anchor_lang::prelude::*;
-use crate::state::{Proposal, ProposalStatus};
+use anchor_lang::{prelude::*, system_program};
+use crate::ErrorCode;
+use crate::instructions::governance::require_not_paused;
+use crate::instructions::tro::{open_task, TASK_SEED};
+use crate::state::{
+    ProgramState, Proposal, ProposalAnalysis, ProposalRevision, ProposalStatus, TaskStatus,
+    TaskType, TroTask, MAX_PROPOSAL_REVISIONS, PAUSE_TASKS, PROGRAM_STATE_SEED,
+    PROPOSAL_IPFS_HASH_MAX_LEN,
+};
 
 #[derive(Accounts)]
-#[instruction(proposal_id: String)]
+#[instruction(proposal_id: String, ipfs_hash: String, analysis: Option<ProposalAnalysis>)]
 pub struct SubmitProposal<'info> {
     #[account(mut)]
     pub submitter: Signer<'info>,
@@ -16,6 +23,19 @@ pub struct SubmitProposal<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
     
+    // 仅在请求分析时传入
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + TroTask::MAX_SIZE,
+        seeds = [TASK_SEED, &analysis.as_ref().map_or(0, |analysis| analysis.task_id).to_le_bytes()],
+        bump
+    )]
+    pub analysis_task: Option<Account<'info, TroTask>>,
+    
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Option<Account<'info, ProgramState>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -23,16 +43,196 @@ pub fn submit_proposal(
     ctx: Context<SubmitProposal>,
     proposal_id: String,
     ipfs_hash: String,
+    analysis: Option<ProposalAnalysis>,
 ) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
+    let submitter = ctx.accounts.submitter.key();
     let clock = Clock::get()?;
     
-    proposal.submitter = ctx.accounts.submitter.key();
+    require!(ipfs_hash.len() <= PROPOSAL_IPFS_HASH_MAX_LEN, ErrorCode::StringTooLong);
+    
+    proposal.submitter = submitter;
     proposal.proposal_id = proposal_id.clone();
-    proposal.ipfs_hash = ipfs_hash;
+    proposal.ipfs_hash = ipfs_hash.clone();
     proposal.timestamp = clock.unix_timestamp;
     proposal.status = ProposalStatus::Submitted;
+    proposal.analysis_task = None;
+    proposal.inference_count = 0;
+    proposal.consensus_hash = String::new();
+    proposal.contributors_root = [0u8; 32];
+    proposal.agreement_bps = 0;
+    proposal.revisions = vec![ProposalRevision {
+        revision: 0,
+        ipfs_hash: ipfs_hash.clone(),
+        timestamp: clock.unix_timestamp,
+    }];
+    
+    // 可选：同时开启治理类TRO分析任务，提案与任务双向关联
+    let Some(analysis) = analysis else {
+        require!(ctx.accounts.analysis_task.is_none(), ErrorCode::InvalidProposalLink);
+        msg!("Proposal submitted: {}", proposal_id);
+        return Ok(());
+    };
+    let (Some(task), Some(program_state)) = (
+        ctx.accounts.analysis_task.as_mut(),
+        ctx.accounts.program_state.as_ref(),
+    ) else {
+        return err!(ErrorCode::InvalidProposalLink);
+    };
+    require_not_paused(program_state, PAUSE_TASKS)?;
+    
+    open_task(
+        task,
+        analysis.task_id,
+        submitter,
+        format!("governance-proposal:{}", proposal_id),
+        TaskType::Governance,
+        analysis.workflow,
+        analysis.criticality,
+        analysis.complexity_score,
+        analysis.stake_pool,
+        analysis.min_node_stake,
+        ipfs_hash,
+        analysis.challenge_window_secs,
+    )?;
+    task.proposal = Some(proposal.key());
+    task.stake_escrowed = true;
+    proposal.analysis_task = Some(task.key());
+    
+    // 分析奖励预存在任务账户中，结算时直接转入奖励金库
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.submitter.to_account_info(),
+                to: task.to_account_info(),
+            },
+        ),
+        analysis.stake_pool,
+    )?;
+    
+    msg!("Proposal submitted: {} with analysis task {}", proposal_id, analysis.task_id);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: String)]
+pub struct AmendProposal<'info> {
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.as_bytes()],
+        bump,
+        has_one = submitter
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    // 提案关联了分析任务时必须传入
+    #[account(mut)]
+    pub analysis_task: Option<Account<'info, TroTask>>,
+}
+
+pub fn amend_proposal(
+    ctx: Context<AmendProposal>,
+    proposal_id: String,
+    ipfs_hash: String,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    // 只能在分析开始前修订
+    require!(
+        proposal.status == ProposalStatus::Submitted,
+        ErrorCode::ProposalNotAmendable
+    );
+    require!(ipfs_hash.len() <= PROPOSAL_IPFS_HASH_MAX_LEN, ErrorCode::StringTooLong);
+    require!(
+        proposal.revisions.len() < MAX_PROPOSAL_REVISIONS,
+        ErrorCode::TooManyRevisions
+    );
+    
+    // 尚未被认领的分析任务保持关联，改为分析新版本内容，预存奖励不变
+    if let Some(linked) = proposal.analysis_task {
+        let task = ctx
+            .accounts
+            .analysis_task
+            .as_mut()
+            .ok_or(ErrorCode::InvalidProposalLink)?;
+        require_keys_eq!(task.key(), linked, ErrorCode::InvalidProposalLink);
+        require!(task.status == TaskStatus::Pending, ErrorCode::ProposalNotAmendable);
+        
+        task.metadata_hash = ipfs_hash.clone();
+        task.updated_ts = clock.unix_timestamp;
+        task.last_actor = proposal.submitter;
+    }
+    
+    let revision = proposal.revisions.len() as u16;
+    proposal.revisions.push(ProposalRevision {
+        revision,
+        ipfs_hash: ipfs_hash.clone(),
+        timestamp: clock.unix_timestamp,
+    });
+    proposal.ipfs_hash = ipfs_hash;
+    
+    msg!("Proposal amended: {} (revision {})", proposal_id, revision);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: String)]
+pub struct CancelProposalAnalysis<'info> {
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"proposal", proposal_id.as_bytes()],
+        bump,
+        has_one = submitter
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        mut,
+        constraint = proposal.analysis_task == Some(analysis_task.key()) @ ErrorCode::InvalidProposalLink
+    )]
+    pub analysis_task: Account<'info, TroTask>,
+}
+
+pub fn cancel_proposal_analysis(
+    ctx: Context<CancelProposalAnalysis>,
+    proposal_id: String,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let task = &mut ctx.accounts.analysis_task;
+    let clock = Clock::get()?;
+    
+    // 只能取消无节点认领的任务，已认领的任务需先由 reclaim_stalled_task 释放
+    require!(
+        task.status == TaskStatus::Pending && task.assigned_nodes.is_empty(),
+        ErrorCode::TaskNotClaimable
+    );
+    require!(task.stake_escrowed, ErrorCode::InvalidProposalLink);
+    
+    // 退还预存的分析奖励，任务账户保留以便处理早期轮次的承诺
+    let refund = task.stake_pool;
+    **task.to_account_info().try_borrow_mut_lamports()? -= refund;
+    **ctx
+        .accounts
+        .submitter
+        .to_account_info()
+        .try_borrow_mut_lamports()? += refund;
+    task.stake_pool = 0;
+    task.status = TaskStatus::Cancelled;
+    task.updated_ts = clock.unix_timestamp;
+    task.last_actor = proposal.submitter;
+    
+    // 解除关联，提案回到普通推理流程
+    proposal.analysis_task = None;
+    proposal.status = ProposalStatus::Submitted;
     
-    msg!("Proposal submitted: {}", proposal_id);
+    msg!("Proposal analysis cancelled: {} (refunded {})", proposal_id, refund);
     Ok(())
 }
@@ -1,86 +1,1090 @@
-﻿use anchor_lang::prelude::*;
+﻿use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+    },
+    system_program,
+};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use solana_instructions_sysvar::get_instruction_relative;
+use solana_sdk_ids::{ed25519_program, sysvar};
 use crate::ErrorCode;
+use crate::instructions::tro::{
+    validate_liveness_params, validate_reward_rates, validate_stake_range, ECONOMY_SEED,
+};
+use crate::state::{EconomyConfig, Treasury, TREASURY_SEED};
 use crate::state::governance::{
+    DelegatedVote,
+    Delegation,
+    GovernanceConfig,
     GovernanceProposal,
     GovernanceProposalStatus,
     GovernanceProposalType,
     GovernanceVoteType,
     ModelConfig,
+    ModelConfigHistory,
+    NodeStakeUpdate,
+    PauseUpdate,
+    ProgramState,
+    ProgramUpgrade,
+    ProposalInstruction,
+    ProposalInstructions,
+    ProposalThreshold,
+    ProposalTimelock,
+    RewardRateUpdate,
+    SignedVoteMessage,
+    TreasurySpend,
     Vote,
+    VoteStrategy,
+    VoterRecord,
+    CONVICTION_PERIOD_SECS,
+    DELEGATION_SEED,
+    GOVERNANCE_AUTHORITY_SEED,
+    MAX_CONVICTION_PERIODS,
+    MAX_DELEGATED_VOTES,
+    MAX_GUARDIANS,
+    MAX_PROPOSAL_INSTRUCTIONS,
+    MAX_PROPOSAL_TYPES,
+    MODEL_CONFIG_HISTORY_SEED,
+    MODEL_CONFIG_SEED,
+    PAUSE_ALL_GROUPS,
+    PAUSE_GOVERNANCE,
+    PROGRAM_STATE_SEED,
+    PROPOSAL_INSTRUCTIONS_SEED,
 };
 
-#[derive(Accounts)]
-pub struct CreateGovernanceProposal<'info> {
-    #[account(mut)]
-    pub proposer: Signer<'info>,
+pub(crate) const GOVERNANCE_CONFIG_SEED: &[u8] = b"governance-config";
+const VOTER_RECORD_SEED: &[u8] = b"voter-record";
+const VOTER_ESCROW_SEED: &[u8] = b"voter-escrow";
+const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(Accounts)]
+pub struct InitializeGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + GovernanceConfig::MAX_SIZE,
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    pub governance_mint: Account<'info, Mint>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetProposalThreshold<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetProposalTimelock<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetProposalDeposit<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardians<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProgramState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramState::MAX_SIZE,
+        seeds = [PROGRAM_STATE_SEED],
+        bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Guardians beyond the first signer are passed as signing `remaining_accounts`.
+#[derive(Accounts)]
+pub struct GuardianPause<'info> {
+    pub guardian: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeModelConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ModelConfig::MAX_SIZE,
+        seeds = [MODEL_CONFIG_SEED],
+        bump
+    )]
+    pub model_config: Account<'info, ModelConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ModelConfigHistory::MAX_SIZE,
+        seeds = [MODEL_CONFIG_HISTORY_SEED],
+        bump
+    )]
+    pub model_config_history: Account<'info, ModelConfigHistory>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateVoterRecord<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(address = governance_config.governance_mint @ ErrorCode::InvalidGovernanceMint)]
+    pub governance_mint: Account<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoterRecord::MAX_SIZE,
+        seeds = [VOTER_RECORD_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        init,
+        payer = voter,
+        seeds = [VOTER_ESCROW_SEED, voter.key().as_ref()],
+        bump,
+        token::mint = governance_mint,
+        token::authority = voter_record
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DepositGovernanceTokens<'info> {
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, voter.key().as_ref()],
+        bump = voter_record.bump,
+        has_one = voter,
+        has_one = escrow
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(mut)]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == voter_record.governance_mint @ ErrorCode::InvalidGovernanceMint,
+        constraint = voter_token_account.owner == voter.key() @ ErrorCode::InvalidTokenAccount
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawGovernanceTokens<'info> {
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, voter.key().as_ref()],
+        bump = voter_record.bump,
+        has_one = voter,
+        has_one = escrow
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(mut)]
+    pub escrow: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = voter_token_account.mint == voter_record.governance_mint @ ErrorCode::InvalidGovernanceMint
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CreateGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(address = governance_config.governance_mint @ ErrorCode::InvalidGovernanceMint)]
+    pub governance_mint: Account<'info, Mint>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    #[account(
+        seeds = [VOTER_RECORD_SEED, proposer.key().as_ref()],
+        bump = proposer_record.bump
+    )]
+    pub proposer_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + GovernanceProposal::MAX_SIZE,
+        seeds = [b"governance_proposal", proposer.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct VoteOnProposal<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + Vote::MAX_SIZE,
+        seeds = [b"vote", voter.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub vote: Account<'info, Vote>,
+    
+    #[account(
+        mut,
+        seeds = [VOTER_RECORD_SEED, voter.key().as_ref()],
+        bump = voter_record.bump,
+        has_one = voter
+    )]
+    pub voter_record: Account<'info, VoterRecord>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: The voter's own delegation PDA, may be uninitialized
+    #[account(
+        seeds = [DELEGATION_SEED, voter.key().as_ref()],
+        bump
+    )]
+    pub delegation: UncheckedAccount<'info>,
+    
+    /// CHECK: The delegate's vote PDA on this proposal, required when the voter's
+    /// delegation applies to it; validated in the handler
+    #[account(mut)]
+    pub delegate_vote: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: (delegation, delegator voter record, delegator vote PDA)
+    // triples for every delegator whose power the voter casts; the delegation and
+    // voter record must be writable
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ChangeVote<'info> {
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote", voter.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = voter
+    )]
+    pub vote: Account<'info, Vote>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct WithdrawVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        mut,
+        seeds = [b"vote", voter.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = voter,
+        close = voter
+    )]
+    pub vote: Account<'info, Vote>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct SubmitSignedVotes<'info> {
+    // 中继者只支付交易费，不需要是投票者
+    pub relayer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 instruction
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+    // remaining_accounts: (voter record, vote PDA, delegation PDA, delegate vote PDA)
+    // for every signature, in the order of the ed25519 instruction
+}
+
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = delegator,
+        space = 8 + Delegation::MAX_SIZE,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump
+    )]
+    pub delegation: Account<'info, Delegation>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    pub delegator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [DELEGATION_SEED, delegator.key().as_ref()],
+        bump = delegation.bump,
+        has_one = delegator,
+        close = delegator
+    )]
+    pub delegation: Account<'info, Delegation>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64, instructions: Vec<ProposalInstruction>)]
+pub struct AttachProposalInstructions<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        seeds = [b"governance_proposal", proposer.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = ProposalInstructions::space_for(&instructions),
+        seeds = [PROPOSAL_INSTRUCTIONS_SEED, proposal.key().as_ref()],
+        bump
+    )]
+    pub proposal_instructions: Account<'info, ProposalInstructions>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct CancelGovernanceProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposer.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        has_one = proposer
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct FinalizeGovernanceProposal<'info> {
+    pub finalizer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    /// CHECK: Receives the refunded deposit, checked against the proposal
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: UncheckedAccount<'info>,
+    
+    /// 仅在未达法定人数、押金被没收时需要
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// CHECK: 仅Custom提案需要，用于确认已附加待执行指令，可能尚未创建
+    #[account(seeds = [PROPOSAL_INSTRUCTIONS_SEED, proposal.key().as_ref()], bump)]
+    pub proposal_instructions: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct QueueProposal<'info> {
+    pub queuer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal.status == GovernanceProposalStatus::Passed @ ErrorCode::ProposalNotPassed
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+/// Guardians beyond the first signer are passed as signing `remaining_accounts`.
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct VetoProposal<'info> {
+    pub guardian: Signer<'info>,
+    
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal.status == GovernanceProposalStatus::Queued @ ErrorCode::ProposalNotQueued
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ExecuteProposal<'info> {
+    pub executor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = proposal.status == GovernanceProposalStatus::Queued @ ErrorCode::ProposalNotQueued
+    )]
+    pub proposal: Account<'info, GovernanceProposal>,
+    
+    #[account(mut, seeds = [MODEL_CONFIG_SEED], bump)]
+    pub model_config: Option<Account<'info, ModelConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [MODEL_CONFIG_HISTORY_SEED],
+        bump = model_config_history.bump
+    )]
+    pub model_config_history: Option<Account<'info, ModelConfigHistory>>,
+    
+    #[account(
+        mut,
+        seeds = [PROPOSAL_INSTRUCTIONS_SEED, proposal.key().as_ref()],
+        bump = proposal_instructions.bump
+    )]
+    pub proposal_instructions: Option<Account<'info, ProposalInstructions>>,
+    
+    /// CHECK: Receives the rent of the executed proposal instructions account
+    #[account(mut, address = proposal.proposer)]
+    pub proposer: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut, seeds = [ECONOMY_SEED], bump)]
+    pub economy_config: Option<Account<'info, EconomyConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
+    /// CHECK: PDA holding the program's upgrade authority, signs the upgrade CPI
+    #[account(seeds = [GOVERNANCE_AUTHORITY_SEED], bump)]
+    pub governance_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: This program's account, only needed for UpgradeProgram
+    #[account(mut, address = crate::ID)]
+    pub program: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: This program's programdata account, validated in the handler
+    #[account(mut)]
+    pub program_data: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Upgrade buffer, checked against the proposal payload and hash
+    #[account(mut)]
+    pub buffer: Option<UncheckedAccount<'info>>,
+    
+    /// CHECK: Receives the buffer's lamports after the upgrade, fixed by the proposal
+    #[account(
+        mut,
+        constraint = proposal
+            .program_upgrade
+            .is_some_and(|upgrade| upgrade.spill == spill.key()) @ ErrorCode::InvalidProgramUpgrade
+    )]
+    pub spill: Option<UncheckedAccount<'info>>,
+    
+    pub rent: Option<Sysvar<'info, Rent>>,
+    
+    pub clock: Option<Sysvar<'info, Clock>>,
+    
+    /// CHECK: BPF upgradeable loader
+    #[account(address = bpf_loader_upgradeable::ID)]
+    pub bpf_loader_upgradeable: Option<UncheckedAccount<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+    
+    /// CHECK: Receives a SOL TreasurySpend, checked against the proposal
+    #[account(mut)]
+    pub treasury_recipient: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub recipient_token_account: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+pub fn initialize_governance(ctx: Context<InitializeGovernance>) -> Result<()> {
+    let config = &mut ctx.accounts.governance_config;
+    
+    config.authority = ctx.accounts.authority.key();
+    config.governance_mint = ctx.accounts.governance_mint.key();
+    config.thresholds = Vec::new();
+    config.timelocks = Vec::new();
+    config.guardians = Vec::new();
+    config.guardian_threshold = 0;
+    config.proposal_deposit = 0;
+    config.min_proposer_balance = 0;
+    config.bump = ctx.bumps.governance_config;
+    
+    msg!("Governance initialized with mint: {}", config.governance_mint);
+    Ok(())
+}
+
+pub fn set_proposal_threshold(
+    ctx: Context<SetProposalThreshold>,
+    proposal_type: GovernanceProposalType,
+    quorum_bps: u16,
+    approval_threshold_bps: u16,
+) -> Result<()> {
+    require!(
+        quorum_bps as u128 <= BPS_DENOMINATOR
+            && approval_threshold_bps as u128 <= BPS_DENOMINATOR
+            && approval_threshold_bps as u128 * 2 > BPS_DENOMINATOR,
+        ErrorCode::InvalidThreshold
+    );
+    
+    let config = &mut ctx.accounts.governance_config;
+    let threshold = ProposalThreshold {
+        proposal_type: proposal_type.clone(),
+        quorum_bps,
+        approval_threshold_bps,
+    };
+    match config
+        .thresholds
+        .iter_mut()
+        .find(|existing| existing.proposal_type == proposal_type)
+    {
+        Some(existing) => *existing = threshold,
+        None => {
+            require!(config.thresholds.len() < MAX_PROPOSAL_TYPES, ErrorCode::InvalidThreshold);
+            config.thresholds.push(threshold);
+        }
+    }
+    
+    msg!(
+        "Threshold for {:?}: quorum {} bps, approval {} bps",
+        proposal_type,
+        quorum_bps,
+        approval_threshold_bps
+    );
+    Ok(())
+}
+
+pub fn set_proposal_timelock(
+    ctx: Context<SetProposalTimelock>,
+    proposal_type: GovernanceProposalType,
+    delay_secs: i64,
+) -> Result<()> {
+    require!(
+        delay_secs >= ProposalTimelock::min_delay_for(&proposal_type),
+        ErrorCode::InvalidTimelock
+    );
+    
+    let config = &mut ctx.accounts.governance_config;
+    let timelock = ProposalTimelock {
+        proposal_type: proposal_type.clone(),
+        delay_secs,
+    };
+    match config
+        .timelocks
+        .iter_mut()
+        .find(|existing| existing.proposal_type == proposal_type)
+    {
+        Some(existing) => *existing = timelock,
+        None => {
+            require!(config.timelocks.len() < MAX_PROPOSAL_TYPES, ErrorCode::InvalidTimelock);
+            config.timelocks.push(timelock);
+        }
+    }
+    
+    msg!("Timelock for {:?}: {} seconds", proposal_type, delay_secs);
+    Ok(())
+}
+
+pub fn set_proposal_deposit(
+    ctx: Context<SetProposalDeposit>,
+    proposal_deposit: u64,
+    min_proposer_balance: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.governance_config;
+    config.proposal_deposit = proposal_deposit;
+    config.min_proposer_balance = min_proposer_balance;
+    
+    msg!(
+        "Proposal deposit: {} lamports, minimum proposer balance: {}",
+        proposal_deposit,
+        min_proposer_balance
+    );
+    Ok(())
+}
+
+pub fn set_guardians(
+    ctx: Context<SetGuardians>,
+    guardians: Vec<Pubkey>,
+    guardian_threshold: u8,
+) -> Result<()> {
+    require!(guardians.len() <= MAX_GUARDIANS, ErrorCode::InvalidGuardianSet);
+    require!(
+        guardian_threshold as usize <= guardians.len()
+            && (guardians.is_empty() || guardian_threshold > 0),
+        ErrorCode::InvalidGuardianSet
+    );
+    for (index, guardian) in guardians.iter().enumerate() {
+        require!(!guardians[..index].contains(guardian), ErrorCode::InvalidGuardianSet);
+    }
+    
+    let config = &mut ctx.accounts.governance_config;
+    config.guardians = guardians;
+    config.guardian_threshold = guardian_threshold;
+    
+    msg!(
+        "Guardian set updated: {} of {}",
+        config.guardian_threshold,
+        config.guardians.len()
+    );
+    Ok(())
+}
+
+/// Requires the signing guardians (`guardian` plus signer `remaining_accounts`) to meet the
+/// configured guardian threshold.
+pub(crate) fn require_guardian_quorum(
+    config: &GovernanceConfig,
+    guardian: &Signer,
+    remaining_accounts: &[AccountInfo],
+) -> Result<()> {
+    let signers: Vec<Pubkey> = std::iter::once(guardian.key())
+        .chain(
+            remaining_accounts
+                .iter()
+                .filter(|account| account.is_signer)
+                .map(|account| account.key()),
+        )
+        .collect();
+    let approvals = config.guardian_approvals(signers.iter());
+    require!(
+        config.guardian_threshold > 0 && approvals >= config.guardian_threshold as usize,
+        ErrorCode::InsufficientGuardianApprovals
+    );
+    Ok(())
+}
+
+pub fn initialize_program_state(ctx: Context<InitializeProgramState>) -> Result<()> {
+    let state = &mut ctx.accounts.program_state;
+    
+    state.paused = false;
+    state.paused_groups = 0;
+    state.paused_at = 0;
+    state.paused_by = Pubkey::default();
+    state.bump = ctx.bumps.program_state;
+    
+    msg!("Program state initialized");
+    Ok(())
+}
+
+/// Guardians can only add pauses; lifting a pause requires an `EmergencyPause` proposal.
+pub fn guardian_pause(ctx: Context<GuardianPause>, pause_all: bool, paused_groups: u8) -> Result<()> {
+    require!(paused_groups & !PAUSE_ALL_GROUPS == 0, ErrorCode::InvalidPauseGroups);
+    require_guardian_quorum(
+        &ctx.accounts.governance_config,
+        &ctx.accounts.guardian,
+        ctx.remaining_accounts,
+    )?;
+    
+    let state = &mut ctx.accounts.program_state;
+    state.paused = state.paused || pause_all;
+    state.paused_groups |= paused_groups;
+    state.paused_at = Clock::get()?.unix_timestamp;
+    state.paused_by = ctx.accounts.guardian.key();
+    
+    msg!("Guardian pause applied: all {}, groups {:#06b}", state.paused, state.paused_groups);
+    Ok(())
+}
+
+pub fn initialize_model_config(
+    ctx: Context<InitializeModelConfig>,
+    initial_config: ModelConfig,
+) -> Result<()> {
+    let model_config = &mut ctx.accounts.model_config;
+    let history = &mut ctx.accounts.model_config_history;
+    let clock = Clock::get()?;
     
-    #[account(
-        init,
-        payer = proposer,
-        space = 8 + GovernanceProposal::MAX_SIZE,
-        seeds = [b"governance_proposal", proposer.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub proposal: Account<'info, GovernanceProposal>,
+    model_config.model_version = initial_config.model_version;
+    model_config.learning_rate = initial_config.learning_rate;
+    model_config.batch_size = initial_config.batch_size;
+    model_config.max_epochs = initial_config.max_epochs;
+    model_config.inference_timeout = initial_config.inference_timeout;
+    model_config.min_node_reputation = initial_config.min_node_reputation;
+    model_config.updated_at = clock.unix_timestamp;
+    model_config.updated_by = ctx.accounts.authority.key();
     
-    /// CHECK: Model config PDA (may not exist yet, will be created if needed)
-    pub current_config: AccountInfo<'info>,
+    history.entries = Vec::new();
+    history.bump = ctx.bumps.model_config_history;
+    history.record((**model_config).clone(), Pubkey::default());
     
-    pub system_program: Program<'info, System>,
+    msg!("Model config initialized at version {}", model_config.model_version);
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(proposal_id: u64)]
-pub struct VoteOnProposal<'info> {
-    #[account(mut)]
-    pub voter: Signer<'info>,
+pub(crate) fn require_not_paused(state: &ProgramState, group: u8) -> Result<()> {
+    require!(!state.is_paused(group), ErrorCode::ProgramPaused);
+    Ok(())
+}
+
+/// Governance stays usable for `EmergencyPause` proposals so a pause can always be lifted.
+fn require_governance_open(state: &ProgramState, proposal_type: &GovernanceProposalType) -> Result<()> {
+    if *proposal_type != GovernanceProposalType::EmergencyPause {
+        require_not_paused(state, PAUSE_GOVERNANCE)?;
+    }
+    Ok(())
+}
+
+pub fn create_voter_record(ctx: Context<CreateVoterRecord>) -> Result<()> {
+    let record = &mut ctx.accounts.voter_record;
     
-    #[account(
-        mut,
-        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub proposal: Account<'info, GovernanceProposal>,
+    record.voter = ctx.accounts.voter.key();
+    record.governance_mint = ctx.accounts.governance_mint.key();
+    record.escrow = ctx.accounts.escrow.key();
+    record.deposited_amount = 0;
+    record.checkpoints = Vec::new();
+    record.locked_until = 0;
+    record.signed_votes = Vec::new();
+    record.bump = ctx.bumps.voter_record;
+    record.escrow_bump = ctx.bumps.escrow;
     
-    #[account(
-        init,
-        payer = voter,
-        space = 8 + Vote::MAX_SIZE,
-        seeds = [b"vote", voter.key().as_ref(), proposal_id.to_le_bytes().as_ref()],
-        bump
-    )]
-    pub vote: Account<'info, Vote>,
+    msg!("Voter record created: {}", record.voter);
+    Ok(())
+}
+
+pub fn deposit_governance_tokens(ctx: Context<DepositGovernanceTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
     
-    pub system_program: Program<'info, System>,
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.voter_token_account.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.voter.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    let record = &mut ctx.accounts.voter_record;
+    record.deposited_amount = record
+        .deposited_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    record.record_checkpoint(Clock::get()?.slot);
+    
+    msg!("Deposited {} governance tokens, total {}", amount, record.deposited_amount);
+    Ok(())
 }
 
-#[derive(Accounts)]
-#[instruction(proposal_id: u64)]
-pub struct ExecuteProposal<'info> {
-    pub executor: Signer<'info>,
+pub fn withdraw_governance_tokens(ctx: Context<WithdrawGovernanceTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
     
-    #[account(
-        mut,
-        seeds = [b"governance_proposal", proposal.proposer.as_ref(), proposal_id.to_le_bytes().as_ref()],
-        bump,
-        constraint = proposal.status == GovernanceProposalStatus::Passed @ ErrorCode::ProposalNotPassed,
-        constraint = Clock::get()?.unix_timestamp >= proposal.voting_ends_at @ ErrorCode::VotingStillActive
-    )]
-    pub proposal: Account<'info, GovernanceProposal>,
+    let clock = Clock::get()?;
+    let record = &ctx.accounts.voter_record;
+    require!(record.deposited_amount >= amount, ErrorCode::InsufficientDeposit);
+    require!(clock.unix_timestamp >= record.locked_until, ErrorCode::TokensLocked);
     
-    /// CHECK: Model config PDA
-    #[account(mut)]
-    pub model_config: AccountInfo<'info>,
+    let voter_key = ctx.accounts.voter.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[VOTER_RECORD_SEED, voter_key.as_ref(), &[record.bump]]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow.to_account_info(),
+                to: ctx.accounts.voter_token_account.to_account_info(),
+                authority: ctx.accounts.voter_record.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    
+    let record = &mut ctx.accounts.voter_record;
+    record.deposited_amount -= amount;
+    record.record_checkpoint(clock.slot);
+    
+    msg!("Withdrew {} governance tokens, remaining {}", amount, record.deposited_amount);
+    Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_governance_proposal(
     ctx: Context<CreateGovernanceProposal>,
     proposal_id: u64,
     proposal_type: GovernanceProposalType,
     description: String,
     target_config: Option<ModelConfig>,
+    reward_rate_update: Option<RewardRateUpdate>,
+    node_stake_update: Option<NodeStakeUpdate>,
+    pause_update: Option<PauseUpdate>,
+    program_upgrade: Option<ProgramUpgrade>,
+    treasury_spend: Option<TreasurySpend>,
     voting_duration: i64,
+    vote_strategy: VoteStrategy,
 ) -> Result<()> {
+    require_governance_open(&ctx.accounts.program_state, &proposal_type)?;
+    // 经济参数类提案必须携带对应的类型化参数，并在创建时提前校验
+    require!(
+        reward_rate_update.is_some() == (proposal_type == GovernanceProposalType::UpdateRewardRate)
+            && node_stake_update.is_some() == (proposal_type == GovernanceProposalType::UpdateNodeStake)
+            && pause_update.is_some() == (proposal_type == GovernanceProposalType::EmergencyPause)
+            && program_upgrade.is_some() == (proposal_type == GovernanceProposalType::UpgradeProgram)
+            && treasury_spend.is_some() == (proposal_type == GovernanceProposalType::TreasurySpend)
+            && target_config.is_some() == (proposal_type == GovernanceProposalType::UpdateModelConfig),
+        ErrorCode::InvalidProposalPayload
+    );
+    if let Some(update) = pause_update {
+        require!(update.paused_groups & !PAUSE_ALL_GROUPS == 0, ErrorCode::InvalidPauseGroups);
+    }
+    if let Some(spend) = treasury_spend {
+        require!(spend.amount > 0, ErrorCode::InvalidTreasurySpend);
+    }
+    if let Some(update) = reward_rate_update {
+        validate_reward_rates(
+            update.base_reward_rate_bps,
+            update.high_perf_multiplier_bps,
+            update.low_perf_penalty_bps,
+        )?;
+    }
+    if let Some(update) = node_stake_update {
+        validate_stake_range(update.stake_floor, update.stake_ceiling)?;
+        validate_liveness_params(
+            update.task_timeout_secs,
+            update.offline_grace_secs,
+            update.offline_penalty_bps_per_hour,
+        )?;
+    }
+    
+    // 防止垃圾提案：提案者需托管足够的治理代币，并缴纳可退还的押金
+    let config = &ctx.accounts.governance_config;
+    require!(
+        ctx.accounts.proposer_record.deposited_amount >= config.min_proposer_balance,
+        ErrorCode::InsufficientProposerBalance
+    );
+    let deposit_amount = config.proposal_deposit;
+    if deposit_amount > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.proposer.to_account_info(),
+                    to: ctx.accounts.proposal.to_account_info(),
+                },
+            ),
+            deposit_amount,
+        )?;
+    }
+    
+    let threshold = config.threshold_for(&proposal_type);
+    let timelock_secs = config.timelock_for(&proposal_type);
     let proposal = &mut ctx.accounts.proposal;
     let clock = Clock::get()?;
     
@@ -88,6 +1092,11 @@ pub fn create_governance_proposal(
     proposal.proposer = ctx.accounts.proposer.key();
     proposal.proposal_type = proposal_type;
     proposal.target_config = target_config;
+    proposal.reward_rate_update = reward_rate_update;
+    proposal.node_stake_update = node_stake_update;
+    proposal.pause_update = pause_update;
+    proposal.program_upgrade = program_upgrade;
+    proposal.treasury_spend = treasury_spend;
     proposal.description = description;
     proposal.votes_for = 0;
     proposal.votes_against = 0;
@@ -96,6 +1105,15 @@ pub fn create_governance_proposal(
     proposal.created_at = clock.unix_timestamp;
     proposal.voting_ends_at = clock.unix_timestamp + voting_duration;
     proposal.executed_at = None;
+    proposal.snapshot_slot = clock.slot;
+    proposal.snapshot_supply = ctx.accounts.governance_mint.supply;
+    proposal.quorum_bps = threshold.quorum_bps;
+    proposal.approval_threshold_bps = threshold.approval_threshold_bps;
+    proposal.timelock_secs = timelock_secs;
+    proposal.eta = 0;
+    proposal.deposit_amount = deposit_amount;
+    proposal.first_vote_at = 0;
+    proposal.vote_strategy = vote_strategy;
     
     msg!("Governance proposal created: {}", proposal_id);
     Ok(())
@@ -105,98 +1123,975 @@ pub fn vote_on_proposal(
     ctx: Context<VoteOnProposal>,
     proposal_id: u64,
     vote_type: GovernanceVoteType,
-    voting_power: u64,
+    conviction_periods: u8,
 ) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
     let vote = &mut ctx.accounts.vote;
+    let voter_record = &mut ctx.accounts.voter_record;
+    let voter = ctx.accounts.voter.key();
     let clock = Clock::get()?;
     
-    require!(proposal.status == GovernanceProposalStatus::Active, ErrorCode::ProposalNotActive);
-    require!(clock.unix_timestamp < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    require_governance_open(&ctx.accounts.program_state, &proposal.proposal_type)?;
+    require_voting_open(proposal, clock.unix_timestamp)?;
+    // 已通过中继计入签名投票的选民不能再链上投票
+    require!(!voter_record.has_signed_vote(&proposal.key()), ErrorCode::AlreadySignedVote);
+    require!(
+        conviction_periods == 0
+            || (proposal.vote_strategy == VoteStrategy::Conviction
+                && conviction_periods <= MAX_CONVICTION_PERIODS),
+        ErrorCode::InvalidConviction
+    );
+    
+    if proposal.first_vote_at == 0 {
+        proposal.first_vote_at = clock.unix_timestamp;
+    }
     
-    vote.voter = ctx.accounts.voter.key();
+    // 直接投票覆盖受托人在本提案上代投的票
+    override_delegate_vote(
+        proposal,
+        &ctx.accounts.delegation,
+        ctx.accounts.delegate_vote.as_ref().map(|account| account.as_ref()),
+        voter,
+        proposal_id,
+    )?;
+    
+    // 投票权取提案创建时的快照，快照后转入的代币不计入
+    let own_power = voter_record.power_at(proposal.snapshot_slot);
+    
+    let own_weight = proposal.vote_strategy.weight(own_power, conviction_periods);
+    
+    // 锁定托管代币直到投票结束，防止转移到其他钱包重复投票；
+    // 信念投票在此基础上按所选周期延长锁定
+    let lock_until = proposal
+        .voting_ends_at
+        .checked_add(CONVICTION_PERIOD_SECS * conviction_periods as i64)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    voter_record.locked_until = voter_record.locked_until.max(lock_until);
+    
+    let proposal_key = proposal.key();
+    let delegated_votes = collect_delegated_votes(
+        proposal,
+        proposal_key,
+        ctx.remaining_accounts,
+        voter,
+        proposal_id,
+    )?;
+    let delegated_power = delegated_votes
+        .iter()
+        .try_fold(0u64, |total, delegated| total.checked_add(delegated.voting_power))
+        .ok_or(ErrorCode::InvalidAmount)?;
+    let voting_power = own_power
+        .checked_add(delegated_power)
+        .ok_or(ErrorCode::InvalidAmount)?;
+    require!(voting_power > 0, ErrorCode::NoVotingPower);
+    let weight = delegated_votes
+        .iter()
+        .try_fold(own_weight, |total, delegated| total.checked_add(delegated.weight))
+        .ok_or(ErrorCode::InvalidAmount)?;
+    
+    vote.voter = voter;
     vote.proposal_id = proposal_id;
     vote.vote_type = vote_type.clone();
     vote.voting_power = voting_power;
     vote.timestamp = clock.unix_timestamp;
+    vote.snapshot_slot = proposal.snapshot_slot;
+    vote.token_account = voter_record.escrow;
+    vote.delegated_power = delegated_power;
+    vote.delegated_votes = delegated_votes;
+    vote.weight = weight;
+    vote.conviction_periods = conviction_periods;
     
     // 更新提案投票统计
+    add_to_tally(proposal, &vote_type, voting_power, weight)?;
+    
+    msg!(
+        "Vote cast on proposal {}: {:?} with power {} ({} delegated), weight {}",
+        proposal_id,
+        vote_type,
+        voting_power,
+        delegated_power,
+        weight
+    );
+    Ok(())
+}
+
+fn vote_address(voter: &Pubkey, proposal_id: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"vote", voter.as_ref(), proposal_id.to_le_bytes().as_ref()],
+        &crate::ID,
+    )
+    .0
+}
+
+/// Adds a vote to the proposal: for/against carry the strategy weight, while
+/// `total_votes` keeps raw token power so quorum stays token-based.
+fn add_to_tally(
+    proposal: &mut GovernanceProposal,
+    vote_type: &GovernanceVoteType,
+    voting_power: u64,
+    weight: u64,
+) -> Result<()> {
+    match vote_type {
+        GovernanceVoteType::For => {
+            proposal.votes_for = proposal.votes_for.checked_add(weight).ok_or(ErrorCode::InvalidAmount)?
+        }
+        GovernanceVoteType::Against => {
+            proposal.votes_against = proposal.votes_against.checked_add(weight).ok_or(ErrorCode::InvalidAmount)?
+        }
+        GovernanceVoteType::Abstain => {}, // 弃权不计入赞成/反对
+    }
+    proposal.total_votes = proposal.total_votes.checked_add(voting_power).ok_or(ErrorCode::InvalidAmount)?;
+    Ok(())
+}
+
+fn remove_from_tally(
+    proposal: &mut GovernanceProposal,
+    vote_type: &GovernanceVoteType,
+    voting_power: u64,
+    weight: u64,
+) -> Result<()> {
     match vote_type {
         GovernanceVoteType::For => {
-            proposal.votes_for = proposal.votes_for.checked_add(voting_power).unwrap()
+            proposal.votes_for = proposal.votes_for.checked_sub(weight).ok_or(ErrorCode::InvalidAmount)?
         }
         GovernanceVoteType::Against => {
-            proposal.votes_against = proposal.votes_against.checked_add(voting_power).unwrap()
+            proposal.votes_against = proposal.votes_against.checked_sub(weight).ok_or(ErrorCode::InvalidAmount)?
+        }
+        GovernanceVoteType::Abstain => {},
+    }
+    proposal.total_votes = proposal.total_votes.checked_sub(voting_power).ok_or(ErrorCode::InvalidAmount)?;
+    Ok(())
+}
+
+fn require_voting_open(proposal: &GovernanceProposal, now: i64) -> Result<()> {
+    require!(proposal.status == GovernanceProposalStatus::Active, ErrorCode::ProposalNotActive);
+    require!(now < proposal.voting_ends_at, ErrorCode::VotingEnded);
+    Ok(())
+}
+
+pub fn change_vote(
+    ctx: Context<ChangeVote>,
+    proposal_id: u64,
+    vote_type: GovernanceVoteType,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote = &mut ctx.accounts.vote;
+    let clock = Clock::get()?;
+    
+    require_governance_open(&ctx.accounts.program_state, &proposal.proposal_type)?;
+    require_voting_open(proposal, clock.unix_timestamp)?;
+    
+    // 委托计入的投票权随投票一起转移
+    remove_from_tally(proposal, &vote.vote_type, vote.voting_power, vote.weight)?;
+    add_to_tally(proposal, &vote_type, vote.voting_power, vote.weight)?;
+    
+    vote.vote_type = vote_type.clone();
+    vote.timestamp = clock.unix_timestamp;
+    
+    msg!("Vote on proposal {} changed to {:?}", proposal_id, vote_type);
+    Ok(())
+}
+
+pub fn withdraw_vote(ctx: Context<WithdrawVote>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let vote = &ctx.accounts.vote;
+    let clock = Clock::get()?;
+    
+    require_governance_open(&ctx.accounts.program_state, &proposal.proposal_type)?;
+    require_voting_open(proposal, clock.unix_timestamp)?;
+    
+    remove_from_tally(proposal, &vote.vote_type, vote.voting_power, vote.weight)?;
+    
+    msg!("Vote on proposal {} withdrawn ({} power)", proposal_id, vote.voting_power);
+    Ok(())
+}
+
+/// Tallies a batch of off-chain signed votes. The ed25519 program instruction
+/// right before this one verifies the signatures; each signer's power is checked
+/// against the proposal snapshot and a receipt on their voter record stops the
+/// same wallet from being counted twice.
+pub fn submit_signed_votes(ctx: Context<SubmitSignedVotes>, proposal_id: u64) -> Result<()> {
+    let proposal_key = ctx.accounts.proposal.key();
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    require_governance_open(&ctx.accounts.program_state, &proposal.proposal_type)?;
+    require_voting_open(proposal, clock.unix_timestamp)?;
+    
+    let ed25519_instruction = get_instruction_relative(-1, &ctx.accounts.instructions_sysvar)?;
+    require_keys_eq!(ed25519_instruction.program_id, ed25519_program::ID, ErrorCode::InvalidSignedVote);
+    let signatures = parse_ed25519_signatures(&ed25519_instruction.data)?;
+    require!(
+        !signatures.is_empty() && ctx.remaining_accounts.len() == signatures.len() * 4,
+        ErrorCode::InvalidSignedVote
+    );
+    
+    let mut total_power: u64 = 0;
+    for ((voter, message), accounts) in signatures.into_iter().zip(ctx.remaining_accounts.chunks(4)) {
+        let (record_info, vote_info, delegation_info, delegate_vote_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        let signed = SignedVoteMessage::decode(message).ok_or(ErrorCode::InvalidSignedVote)?;
+        require_keys_eq!(signed.proposal, proposal_key, ErrorCode::InvalidSignedVote);
+        require!(signed.proposal_id == proposal_id, ErrorCode::InvalidSignedVote);
+        
+        require_keys_eq!(*record_info.owner, crate::ID, ErrorCode::InvalidSignedVote);
+        require!(record_info.is_writable, ErrorCode::InvalidSignedVote);
+        let mut data = record_info.try_borrow_mut_data()?;
+        let mut record = VoterRecord::try_deserialize(&mut &data[..])?;
+        let record_address = Pubkey::create_program_address(
+            &[VOTER_RECORD_SEED, record.voter.as_ref(), &[record.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidSignedVote)?;
+        require_keys_eq!(record_info.key(), record_address, ErrorCode::InvalidSignedVote);
+        require_keys_eq!(record.voter, voter, ErrorCode::InvalidSignedVote);
+        
+        // 链上投票与签名投票互斥
+        require_keys_eq!(vote_info.key(), vote_address(&voter, proposal_id), ErrorCode::InvalidSignedVote);
+        require!(vote_info.data_is_empty(), ErrorCode::AlreadySignedVote);
+        require!(!record.has_signed_vote(&proposal_key), ErrorCode::AlreadySignedVote);
+        
+        // 签名中的投票权必须与快照一致，避免中继者或选民虚报
+        let voting_power = record.power_at(proposal.snapshot_slot);
+        require!(voting_power > 0, ErrorCode::NoVotingPower);
+        require!(voting_power == signed.voting_power, ErrorCode::SignedVotePowerMismatch);
+        
+        require_keys_eq!(
+            delegation_info.key(),
+            Pubkey::find_program_address(&[DELEGATION_SEED, voter.as_ref()], &crate::ID).0,
+            ErrorCode::InvalidSignedVote
+        );
+        override_delegate_vote(
+            proposal,
+            delegation_info,
+            Some(delegate_vote_info),
+            voter,
+            proposal_id,
+        )?;
+        
+        require!(
+            record.record_signed_vote(proposal_key, proposal.voting_ends_at, clock.unix_timestamp),
+            ErrorCode::TooManySignedVotes
+        );
+        record.locked_until = record.locked_until.max(proposal.voting_ends_at);
+        record.try_serialize(&mut &mut data[..])?;
+        
+        if proposal.first_vote_at == 0 {
+            proposal.first_vote_at = clock.unix_timestamp;
+        }
+        let weight = proposal.vote_strategy.weight(voting_power, 0);
+        add_to_tally(proposal, &signed.vote_type, voting_power, weight)?;
+        total_power = total_power.checked_add(voting_power).ok_or(ErrorCode::InvalidAmount)?;
+    }
+    
+    msg!(
+        "Signed votes relayed on proposal {}: {} voters, {} power",
+        proposal_id,
+        ctx.remaining_accounts.len() / 4,
+        total_power
+    );
+    Ok(())
+}
+
+/// Extracts (signer, message) pairs from an ed25519 program instruction. Only
+/// entries whose key, signature and message all live in that instruction are
+/// accepted, so the runtime has verified exactly the bytes returned here.
+fn parse_ed25519_signatures(data: &[u8]) -> Result<Vec<(Pubkey, &[u8])>> {
+    // [count: u8][padding: u8] 后接每个签名14字节的偏移表
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    
+    let read_u16 = |at: usize| -> Result<usize> {
+        data.get(at..at + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+            .ok_or_else(|| error!(ErrorCode::InvalidSignedVote))
+    };
+    let count = *data.first().ok_or(ErrorCode::InvalidSignedVote)? as usize;
+    
+    let mut signatures = Vec::with_capacity(count);
+    for index in 0..count {
+        let offsets = OFFSETS_START + index * OFFSETS_SIZE;
+        // signature / public key / message 的指令索引必须指向ed25519指令自身
+        for at in [offsets + 2, offsets + 6, offsets + 12] {
+            require!(read_u16(at)? == u16::MAX as usize, ErrorCode::InvalidSignedVote);
+        }
+        let key_offset = read_u16(offsets + 4)?;
+        let message_offset = read_u16(offsets + 8)?;
+        let message_size = read_u16(offsets + 10)?;
+        
+        let key = data
+            .get(key_offset..key_offset + 32)
+            .ok_or(ErrorCode::InvalidSignedVote)?;
+        let message = data
+            .get(message_offset..message_offset + message_size)
+            .ok_or(ErrorCode::InvalidSignedVote)?;
+        signatures.push((
+            Pubkey::try_from(key).map_err(|_| ErrorCode::InvalidSignedVote)?,
+            message,
+        ));
+    }
+    Ok(signatures)
+}
+
+/// Pulls the voter's power back out of their delegate's vote when the delegate
+/// has already voted on this proposal on their behalf. The delegate's vote is
+/// required whenever the voter's delegation applied at the proposal snapshot,
+/// and any delegate vote passed in is checked for the voter's entry.
+fn override_delegate_vote(
+    proposal: &mut GovernanceProposal,
+    delegation_info: &AccountInfo,
+    delegate_vote_info: Option<&AccountInfo>,
+    voter: Pubkey,
+    proposal_id: u64,
+) -> Result<()> {
+    if delegation_info.owner == &crate::ID && !delegation_info.data_is_empty() {
+        let delegation = Delegation::try_deserialize(&mut &delegation_info.try_borrow_data()?[..])?;
+        if delegation.applies_to(&proposal.proposal_type, proposal.snapshot_slot, proposal.created_at) {
+            let delegate_vote_info = delegate_vote_info.ok_or(ErrorCode::MissingDelegateVote)?;
+            require_keys_eq!(
+                delegate_vote_info.key(),
+                vote_address(&delegation.delegate, proposal_id),
+                ErrorCode::InvalidDelegation
+            );
+        }
+    }
+    
+    // 受托人尚未投票：其投票时会跳过已直接投票的委托人
+    let Some(delegate_vote_info) = delegate_vote_info else {
+        return Ok(());
+    };
+    if delegate_vote_info.owner != &crate::ID || delegate_vote_info.data_is_empty() {
+        return Ok(());
+    }
+    
+    let mut data = delegate_vote_info.try_borrow_mut_data()?;
+    let mut delegate_vote = Vote::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(
+        delegate_vote_info.key(),
+        vote_address(&delegate_vote.voter, proposal_id),
+        ErrorCode::InvalidDelegation
+    );
+    let Some(index) = delegate_vote
+        .delegated_votes
+        .iter()
+        .position(|delegated| delegated.delegator == voter)
+    else {
+        return Ok(());
+    };
+    let removed = delegate_vote.delegated_votes.remove(index);
+    delegate_vote.voting_power = delegate_vote.voting_power.checked_sub(removed.voting_power).ok_or(ErrorCode::InvalidAmount)?;
+    delegate_vote.delegated_power = delegate_vote.delegated_power.checked_sub(removed.voting_power).ok_or(ErrorCode::InvalidAmount)?;
+    delegate_vote.weight = delegate_vote.weight.checked_sub(removed.weight).ok_or(ErrorCode::InvalidAmount)?;
+    remove_from_tally(proposal, &delegate_vote.vote_type, removed.voting_power, removed.weight)?;
+    delegate_vote.try_serialize(&mut &mut data[..])?;
+    
+    msg!("Delegator {} overrode delegate {} with {} power", voter, delegate_vote.voter, removed.voting_power);
+    Ok(())
+}
+
+/// Reads (delegation, delegator voter record, delegator vote PDA) triples and
+/// returns the snapshot power each applicable delegator hands to `delegate`.
+/// Delegators who already voted directly are skipped.
+fn collect_delegated_votes(
+    proposal: &GovernanceProposal,
+    proposal_key: Pubkey,
+    remaining_accounts: &[AccountInfo],
+    delegate: Pubkey,
+    proposal_id: u64,
+) -> Result<Vec<DelegatedVote>> {
+    require!(remaining_accounts.chunks_exact(3).remainder().is_empty(), ErrorCode::InvalidDelegation);
+    
+    let mut delegated_votes: Vec<DelegatedVote> = Vec::new();
+    for accounts in remaining_accounts.chunks(3) {
+        let (delegation_info, record_info, delegator_vote_info) = (&accounts[0], &accounts[1], &accounts[2]);
+        
+        require_keys_eq!(*delegation_info.owner, crate::ID, ErrorCode::InvalidDelegation);
+        require!(delegation_info.is_writable, ErrorCode::InvalidDelegation);
+        let mut delegation = Delegation::try_deserialize(&mut &delegation_info.try_borrow_data()?[..])?;
+        let delegation_address = Pubkey::create_program_address(
+            &[DELEGATION_SEED, delegation.delegator.as_ref(), &[delegation.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidDelegation)?;
+        require_keys_eq!(delegation_info.key(), delegation_address, ErrorCode::InvalidDelegation);
+        require_keys_eq!(delegation.delegate, delegate, ErrorCode::InvalidDelegation);
+        require!(
+            delegation.applies_to(&proposal.proposal_type, proposal.snapshot_slot, proposal.created_at),
+            ErrorCode::DelegationNotApplicable
+        );
+        require!(
+            !delegated_votes.iter().any(|delegated| delegated.delegator == delegation.delegator),
+            ErrorCode::InvalidDelegation
+        );
+        
+        // 委托人已直接投票，则以其本人投票为准
+        require_keys_eq!(
+            delegator_vote_info.key(),
+            vote_address(&delegation.delegator, proposal_id),
+            ErrorCode::InvalidDelegation
+        );
+        if !delegator_vote_info.data_is_empty() {
+            continue;
+        }
+        
+        require_keys_eq!(*record_info.owner, crate::ID, ErrorCode::InvalidDelegation);
+        require!(record_info.is_writable, ErrorCode::InvalidDelegation);
+        let mut data = record_info.try_borrow_mut_data()?;
+        let mut record = VoterRecord::try_deserialize(&mut &data[..])?;
+        let record_address = Pubkey::create_program_address(
+            &[VOTER_RECORD_SEED, record.voter.as_ref(), &[record.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::InvalidDelegation)?;
+        require_keys_eq!(record_info.key(), record_address, ErrorCode::InvalidDelegation);
+        require_keys_eq!(record.voter, delegation.delegator, ErrorCode::InvalidDelegation);
+        // 委托人已通过签名投票，同样以其本人投票为准
+        if record.has_signed_vote(&proposal_key) {
+            continue;
+        }
+        
+        let voting_power = record.power_at(proposal.snapshot_slot);
+        if voting_power == 0 {
+            continue;
         }
-        GovernanceVoteType::Abstain => {}, // 弃权不计入
+        require!(delegated_votes.len() < MAX_DELEGATED_VOTES, ErrorCode::TooManyDelegations);
+        
+        // 委托人的托管代币同样锁定到投票结束
+        record.locked_until = record.locked_until.max(proposal.voting_ends_at);
+        record.try_serialize(&mut &mut data[..])?;
+        
+        // 委托在计入的提案投票结束前不能关闭，否则委托人直接投票时无法扣回
+        delegation.locked_until = delegation.locked_until.max(proposal.voting_ends_at);
+        delegation.try_serialize(&mut &mut delegation_info.try_borrow_mut_data()?[..])?;
+        
+        // 委托人未选择额外锁定，信念投票下按基础权重计入
+        delegated_votes.push(DelegatedVote {
+            delegator: delegation.delegator,
+            voting_power,
+            weight: proposal.vote_strategy.weight(voting_power, 0),
+        });
+    }
+    Ok(delegated_votes)
+}
+
+pub fn delegate_votes(
+    ctx: Context<DelegateVotes>,
+    delegate: Pubkey,
+    scope: Option<GovernanceProposalType>,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    let delegation = &mut ctx.accounts.delegation;
+    let clock = Clock::get()?;
+    
+    require_keys_neq!(delegate, ctx.accounts.delegator.key(), ErrorCode::InvalidDelegation);
+    if let Some(expires_at) = expires_at {
+        require!(expires_at > clock.unix_timestamp, ErrorCode::InvalidDelegation);
+    }
+    
+    delegation.delegator = ctx.accounts.delegator.key();
+    delegation.delegate = delegate;
+    delegation.scope = scope;
+    delegation.expires_at = expires_at;
+    delegation.created_slot = clock.slot;
+    delegation.revoked_slot = None;
+    delegation.locked_until = 0;
+    delegation.bump = ctx.bumps.delegation;
+    
+    msg!("Votes of {} delegated to {}", delegation.delegator, delegate);
+    Ok(())
+}
+
+pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+    let delegation = &mut ctx.accounts.delegation;
+    require!(delegation.revoked_slot.is_none(), ErrorCode::DelegationNotApplicable);
+    
+    // 撤销仅对之后创建的提案生效，已创建提案仍按快照计入受托人
+    delegation.revoked_slot = Some(Clock::get()?.slot);
+    
+    msg!("Delegation of {} revoked", delegation.delegator);
+    Ok(())
+}
+
+/// Closes a revoked or expired delegation so the delegator can delegate again,
+/// once every proposal it was counted on has finished voting.
+pub fn close_delegation(ctx: Context<CloseDelegation>) -> Result<()> {
+    let delegation = &ctx.accounts.delegation;
+    let now = Clock::get()?.unix_timestamp;
+    let expired = delegation.expires_at.is_some_and(|expires_at| now >= expires_at);
+    require!(
+        delegation.revoked_slot.is_some() || expired,
+        ErrorCode::DelegationStillActive
+    );
+    require!(now >= delegation.locked_until, ErrorCode::DelegationInUse);
+    
+    msg!("Delegation of {} closed", delegation.delegator);
+    Ok(())
+}
+
+pub fn attach_proposal_instructions(
+    ctx: Context<AttachProposalInstructions>,
+    proposal_id: u64,
+    instructions: Vec<ProposalInstruction>,
+) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    
+    require!(
+        proposal.proposal_type == GovernanceProposalType::Custom,
+        ErrorCode::InvalidProposalPayload
+    );
+    require!(proposal.status == GovernanceProposalStatus::Active, ErrorCode::ProposalNotActive);
+    // 指令必须在投票开始前确定，投票者看到的即是将被执行的内容
+    require!(proposal.first_vote_at == 0, ErrorCode::ProposalHasVotes);
+    require!(
+        !instructions.is_empty() && instructions.len() <= MAX_PROPOSAL_INSTRUCTIONS,
+        ErrorCode::InvalidProposalInstruction
+    );
+    
+    // 只有治理PDA能在CPI中签名
+    let (governance_authority, _) =
+        Pubkey::find_program_address(&[GOVERNANCE_AUTHORITY_SEED], &crate::ID);
+    require!(
+        instructions
+            .iter()
+            .flat_map(|instruction| instruction.accounts.iter())
+            .all(|meta| !meta.is_signer || meta.pubkey == governance_authority),
+        ErrorCode::InvalidProposalInstruction
+    );
+    // 治理PDA同时是程序升级权限，升级只能走带缓冲区哈希校验的升级提案
+    require!(
+        instructions
+            .iter()
+            .all(|instruction| instruction.program_id != bpf_loader_upgradeable::ID),
+        ErrorCode::InvalidProposalInstruction
+    );
+    
+    let proposal_instructions = &mut ctx.accounts.proposal_instructions;
+    proposal_instructions.proposal = proposal.key();
+    proposal_instructions.instructions = instructions;
+    proposal_instructions.bump = ctx.bumps.proposal_instructions;
+    
+    msg!(
+        "Attached {} instructions to proposal {}",
+        proposal_instructions.instructions.len(),
+        proposal_id
+    );
+    Ok(())
+}
+
+fn find_cpi_account<'info>(
+    key: &Pubkey,
+    authority: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<AccountInfo<'info>> {
+    if authority.key == key {
+        return Ok(authority.clone());
+    }
+    remaining_accounts
+        .iter()
+        .find(|account| account.key == key)
+        .cloned()
+        .ok_or_else(|| error!(ErrorCode::MissingGovernanceAccount))
+}
+
+/// Moves the escrowed deposit out of the proposal account.
+fn release_deposit(proposal: &mut Account<GovernanceProposal>, to: &AccountInfo) -> Result<u64> {
+    let amount = proposal.deposit_amount;
+    if amount == 0 {
+        return Ok(0);
+    }
+    proposal.deposit_amount = 0;
+    **proposal.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **to.try_borrow_mut_lamports()? += amount;
+    Ok(amount)
+}
+
+pub fn cancel_governance_proposal(
+    ctx: Context<CancelGovernanceProposal>,
+    proposal_id: u64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    
+    require!(proposal.status == GovernanceProposalStatus::Active, ErrorCode::ProposalNotActive);
+    // 一旦有人投票即不可撤回
+    require!(proposal.first_vote_at == 0, ErrorCode::ProposalHasVotes);
+    
+    let refunded = release_deposit(proposal, &ctx.accounts.proposer.to_account_info())?;
+    proposal.status = GovernanceProposalStatus::Cancelled;
+    
+    msg!("Proposal {} cancelled, {} lamports refunded", proposal_id, refunded);
+    Ok(())
+}
+
+pub fn finalize_governance_proposal(
+    ctx: Context<FinalizeGovernanceProposal>,
+    proposal_id: u64,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    require!(proposal.status == GovernanceProposalStatus::Active, ErrorCode::ProposalNotActive);
+    require!(clock.unix_timestamp >= proposal.voting_ends_at, ErrorCode::VotingStillActive);
+    
+    // 法定人数：总投票量（含弃权）占快照供应量的比例
+    let quorum_reached = (proposal.total_votes as u128) * BPS_DENOMINATOR
+        >= (proposal.snapshot_supply as u128) * (proposal.quorum_bps as u128);
+    // 通过阈值：支持票占有效票（支持+反对）的比例，按提案计票方式折算后的票数计算
+    let decisive_votes = (proposal.votes_for as u128) + (proposal.votes_against as u128);
+    let approved = decisive_votes > 0
+        && (proposal.votes_for as u128) * BPS_DENOMINATOR
+            >= decisive_votes * (proposal.approval_threshold_bps as u128);
+    // 未附加指令的Custom提案无法执行，不能通过
+    let executable = if proposal.proposal_type == GovernanceProposalType::Custom {
+        let proposal_instructions = ctx
+            .accounts
+            .proposal_instructions
+            .as_ref()
+            .ok_or(ErrorCode::MissingGovernanceAccount)?;
+        !proposal_instructions.data_is_empty()
+    } else {
+        true
+    };
+    
+    proposal.status = if quorum_reached && approved && executable {
+        GovernanceProposalStatus::Passed
+    } else {
+        GovernanceProposalStatus::Rejected
+    };
+    
+    // 达到法定人数则退还押金，否则没收至国库
+    if quorum_reached {
+        release_deposit(proposal, &ctx.accounts.proposer)?;
+    } else if proposal.deposit_amount > 0 {
+        let treasury = ctx
+            .accounts
+            .treasury
+            .as_ref()
+            .ok_or(ErrorCode::MissingGovernanceAccount)?;
+        release_deposit(proposal, &treasury.to_account_info())?;
     }
-    proposal.total_votes = proposal.total_votes.checked_add(voting_power).unwrap();
     
-    msg!("Vote cast on proposal {}: {:?} with power {}", proposal_id, vote_type, voting_power);
+    msg!(
+        "Proposal {} finalized: {:?} (quorum reached: {}, approved: {})",
+        proposal_id,
+        proposal.status,
+        quorum_reached,
+        approved
+    );
     Ok(())
 }
 
-pub fn execute_proposal(
-    ctx: Context<ExecuteProposal>,
+pub fn queue_proposal(ctx: Context<QueueProposal>, proposal_id: u64) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    
+    proposal.eta = clock
+        .unix_timestamp
+        .checked_add(proposal.timelock_secs)
+        .ok_or(ErrorCode::MathOverflow)?;
+    proposal.status = GovernanceProposalStatus::Queued;
+    
+    msg!("Proposal {} queued, executable at {}", proposal_id, proposal.eta);
+    Ok(())
+}
+
+pub fn veto_proposal(ctx: Context<VetoProposal>, proposal_id: u64) -> Result<()> {
+    require_guardian_quorum(
+        &ctx.accounts.governance_config,
+        &ctx.accounts.guardian,
+        ctx.remaining_accounts,
+    )?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+    require!(clock.unix_timestamp < proposal.eta, ErrorCode::TimelockExpired);
+    
+    proposal.status = GovernanceProposalStatus::Vetoed;
+    
+    msg!("Proposal {} vetoed by guardians", proposal_id);
+    Ok(())
+}
+
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
     _proposal_id: u64,
 ) -> Result<()> {
+    let proposal_key = ctx.accounts.proposal.key();
     let proposal = &mut ctx.accounts.proposal;
-    let model_config = &mut ctx.accounts.model_config;
     let clock = Clock::get()?;
     
-    // 检查投票是否通过（简单多数）
-    let total_voting = proposal.votes_for + proposal.votes_against;
-    require!(total_voting > 0, ErrorCode::NoVotes);
-    require!(proposal.votes_for > proposal.votes_against, ErrorCode::ProposalNotPassed);
+    require_governance_open(&ctx.accounts.program_state, &proposal.proposal_type)?;
+    require!(clock.unix_timestamp >= proposal.eta, ErrorCode::TimelockNotExpired);
     
     // 根据提案类型执行操作
     match proposal.proposal_type {
         GovernanceProposalType::UpdateModelConfig => {
-            if let Some(ref new_config) = proposal.target_config {
-                // 检查模型配置账户是否存在
-                if model_config.data_is_empty() {
-                    // 如果账户不存在，需要先初始化（这里简化处理，实际应该创建账户）
-                    msg!("Model config account does not exist, skipping update");
-                } else {
-                    // 反序列化模型配置账户
-                    let mut config_data = model_config.try_borrow_mut_data()?;
-                    let mut config = ModelConfig::try_deserialize(&mut &config_data[8..])?;
-                    
-                    // 更新配置
-                    config.model_version = new_config.model_version;
-                    config.learning_rate = new_config.learning_rate;
-                    config.batch_size = new_config.batch_size;
-                    config.max_epochs = new_config.max_epochs;
-                    config.inference_timeout = new_config.inference_timeout;
-                    config.min_node_reputation = new_config.min_node_reputation;
-                    config.updated_at = clock.unix_timestamp;
-                    config.updated_by = ctx.accounts.executor.key();
-                    
-                    // 序列化回账户
-                    config.try_serialize(&mut &mut config_data[8..])?;
-                    msg!("Model configuration updated");
-                }
-            }
+            let new_config = proposal.target_config.clone().ok_or(ErrorCode::InvalidProposalPayload)?;
+            let (Some(model_config), Some(history)) = (
+                ctx.accounts.model_config.as_mut(),
+                ctx.accounts.model_config_history.as_mut(),
+            ) else {
+                return err!(ErrorCode::MissingGovernanceAccount);
+            };
+            // 版本号必须递增，过期的提案不能覆盖更新的配置
+            require!(
+                new_config.model_version > model_config.model_version,
+                ErrorCode::StaleModelVersion
+            );
+            
+            model_config.model_version = new_config.model_version;
+            model_config.learning_rate = new_config.learning_rate;
+            model_config.batch_size = new_config.batch_size;
+            model_config.max_epochs = new_config.max_epochs;
+            model_config.inference_timeout = new_config.inference_timeout;
+            model_config.min_node_reputation = new_config.min_node_reputation;
+            model_config.updated_at = clock.unix_timestamp;
+            model_config.updated_by = ctx.accounts.executor.key();
+            
+            history.record((**model_config).clone(), proposal_key);
+            msg!("Model configuration updated to version {}", model_config.model_version);
         },
         GovernanceProposalType::UpdateRewardRate => {
-            // 奖励率更新需要单独的配置账户
-            // 这里记录到提案描述中，实际更新由后端处理
-            msg!("Reward rate update proposal executed: {}", proposal.description);
+            let update = proposal.reward_rate_update.ok_or(ErrorCode::InvalidProposalPayload)?;
+            let economy = ctx
+                .accounts
+                .economy_config
+                .as_mut()
+                .ok_or(ErrorCode::MissingGovernanceAccount)?;
+            validate_reward_rates(
+                update.base_reward_rate_bps,
+                update.high_perf_multiplier_bps,
+                update.low_perf_penalty_bps,
+            )?;
+            
+            economy.base_reward_rate_bps = update.base_reward_rate_bps;
+            economy.high_perf_multiplier_bps = update.high_perf_multiplier_bps;
+            economy.low_perf_penalty_bps = update.low_perf_penalty_bps;
+            msg!(
+                "Reward rates updated: base {} / high {} / low {} bps",
+                update.base_reward_rate_bps,
+                update.high_perf_multiplier_bps,
+                update.low_perf_penalty_bps
+            );
         },
         GovernanceProposalType::UpdateNodeStake => {
-            // 节点质押要求更新需要单独的配置账户
-            // 这里记录到提案描述中，实际更新由后端处理
-            msg!("Node stake requirement update proposal executed: {}", proposal.description);
+            let update = proposal.node_stake_update.ok_or(ErrorCode::InvalidProposalPayload)?;
+            let economy = ctx
+                .accounts
+                .economy_config
+                .as_mut()
+                .ok_or(ErrorCode::MissingGovernanceAccount)?;
+            validate_stake_range(update.stake_floor, update.stake_ceiling)?;
+            validate_liveness_params(
+                update.task_timeout_secs,
+                update.offline_grace_secs,
+                update.offline_penalty_bps_per_hour,
+            )?;
+            
+            economy.stake_floor = update.stake_floor;
+            economy.stake_ceiling = update.stake_ceiling;
+            economy.task_timeout_secs = update.task_timeout_secs;
+            economy.offline_grace_secs = update.offline_grace_secs;
+            economy.offline_penalty_bps_per_hour = update.offline_penalty_bps_per_hour;
+            msg!(
+                "Node stake range updated: {} - {}, task timeout {}s",
+                update.stake_floor,
+                update.stake_ceiling,
+                update.task_timeout_secs
+            );
         },
         GovernanceProposalType::EmergencyPause => {
-            // 紧急暂停：设置全局暂停标志
-            // 需要创建全局状态账户来存储暂停状态
-            msg!("Emergency pause proposal executed");
+            // 紧急暂停：治理提案可设置或解除暂停（守护者只能暂停）
+            let update = proposal.pause_update.ok_or(ErrorCode::InvalidProposalPayload)?;
+            let state = &mut ctx.accounts.program_state;
+            state.paused = update.paused;
+            state.paused_groups = update.paused_groups;
+            if update.paused || update.paused_groups != 0 {
+                state.paused_at = clock.unix_timestamp;
+                state.paused_by = proposal.key();
+            }
+            msg!(
+                "Emergency pause proposal executed: all {}, groups {:#06b}",
+                state.paused,
+                state.paused_groups
+            );
         },
         GovernanceProposalType::UpgradeProgram => {
-            // 程序升级：记录升级信息
-            // 实际升级需要BPF升级流程
-            msg!("Program upgrade proposal executed: {}", proposal.description);
+            let upgrade = proposal.program_upgrade.ok_or(ErrorCode::InvalidProposalPayload)?;
+            let (
+                Some(program),
+                Some(program_data),
+                Some(buffer),
+                Some(spill),
+                Some(rent),
+                Some(clock_sysvar),
+                Some(loader),
+            ) = (
+                ctx.accounts.program.as_ref(),
+                ctx.accounts.program_data.as_ref(),
+                ctx.accounts.buffer.as_ref(),
+                ctx.accounts.spill.as_ref(),
+                ctx.accounts.rent.as_ref(),
+                ctx.accounts.clock.as_ref(),
+                ctx.accounts.bpf_loader_upgradeable.as_ref(),
+            ) else {
+                return err!(ErrorCode::MissingGovernanceAccount);
+            };
+            require_keys_eq!(buffer.key(), upgrade.buffer, ErrorCode::InvalidProgramUpgrade);
+            require_keys_eq!(
+                program_data.key(),
+                bpf_loader_upgradeable::get_program_data_address(&crate::ID),
+                ErrorCode::InvalidProgramUpgrade
+            );
+            
+            // 执行前校验缓冲区内容与投票通过的哈希一致
+            {
+                let data = buffer.try_borrow_data()?;
+                let metadata_len = UpgradeableLoaderState::size_of_buffer_metadata();
+                require!(data.len() > metadata_len, ErrorCode::InvalidProgramUpgrade);
+                let buffer_hash = solana_sha256_hasher::hash(&data[metadata_len..]).to_bytes();
+                require!(buffer_hash == upgrade.buffer_hash, ErrorCode::BufferHashMismatch);
+            }
+            
+            let authority = &ctx.accounts.governance_authority;
+            invoke_signed(
+                &bpf_loader_upgradeable::upgrade(
+                    &crate::ID,
+                    &buffer.key(),
+                    &authority.key(),
+                    &spill.key(),
+                ),
+                &[
+                    program_data.to_account_info(),
+                    program.to_account_info(),
+                    buffer.to_account_info(),
+                    spill.to_account_info(),
+                    rent.to_account_info(),
+                    clock_sysvar.to_account_info(),
+                    authority.to_account_info(),
+                    loader.to_account_info(),
+                ],
+                &[&[GOVERNANCE_AUTHORITY_SEED, &[ctx.bumps.governance_authority]]],
+            )?;
+            msg!("Program upgraded from buffer {}", upgrade.buffer);
+        },
+        GovernanceProposalType::Custom => {
+            let proposal_instructions = ctx
+                .accounts
+                .proposal_instructions
+                .as_ref()
+                .ok_or(ErrorCode::MissingGovernanceAccount)?;
+            let proposer = ctx
+                .accounts
+                .proposer
+                .as_ref()
+                .ok_or(ErrorCode::MissingGovernanceAccount)?;
+            
+            // 先落盘执行状态，防止被调用的指令重入执行同一提案
+            proposal.status = GovernanceProposalStatus::Executed;
+            proposal.executed_at = Some(clock.unix_timestamp);
+            proposal.exit(&crate::ID)?;
+            
+            let authority = ctx.accounts.governance_authority.to_account_info();
+            let signer_seeds: &[&[u8]] = &[GOVERNANCE_AUTHORITY_SEED, &[ctx.bumps.governance_authority]];
+            for instruction in &proposal_instructions.instructions {
+                require_keys_neq!(
+                    instruction.program_id,
+                    bpf_loader_upgradeable::ID,
+                    ErrorCode::InvalidProposalInstruction
+                );
+                let mut account_infos = Vec::with_capacity(instruction.accounts.len() + 1);
+                for meta in &instruction.accounts {
+                    account_infos.push(find_cpi_account(&meta.pubkey, &authority, ctx.remaining_accounts)?);
+                }
+                account_infos.push(find_cpi_account(&instruction.program_id, &authority, ctx.remaining_accounts)?);
+                
+                invoke_signed(
+                    &Instruction {
+                        program_id: instruction.program_id,
+                        accounts: instruction
+                            .accounts
+                            .iter()
+                            .map(|meta| AccountMeta {
+                                pubkey: meta.pubkey,
+                                is_signer: meta.is_signer,
+                                is_writable: meta.is_writable,
+                            })
+                            .collect(),
+                        data: instruction.data.clone(),
+                    },
+                    &account_infos,
+                    &[signer_seeds],
+                )?;
+            }
+            msg!("Executed {} proposal instructions", proposal_instructions.instructions.len());
+            
+            // 指令账户租金退还给提案人
+            proposal_instructions.close(proposer.to_account_info())?;
+        },
+        GovernanceProposalType::TreasurySpend => {
+            let spend = proposal.treasury_spend.ok_or(ErrorCode::InvalidProposalPayload)?;
+            let treasury = ctx
+                .accounts
+                .treasury
+                .as_mut()
+                .ok_or(ErrorCode::MissingGovernanceAccount)?;
+            // 超出本周期额度时提案保持Queued状态，可在之后的周期重新执行
+            require!(
+                treasury.record_spend(spend.mint, spend.amount, clock.unix_timestamp),
+                ErrorCode::TreasuryLimitExceeded
+            );
+            
+            match spend.mint {
+                None => {
+                    let recipient = ctx
+                        .accounts
+                        .treasury_recipient
+                        .as_ref()
+                        .ok_or(ErrorCode::MissingGovernanceAccount)?;
+                    require_keys_eq!(recipient.key(), spend.recipient, ErrorCode::InvalidTreasurySpend);
+                    
+                    // 国库账户需保留租金豁免余额
+                    let treasury_info = treasury.to_account_info();
+                    let reserve = Rent::get()?.minimum_balance(treasury_info.data_len());
+                    require!(
+                        treasury_info.lamports().saturating_sub(reserve) >= spend.amount,
+                        ErrorCode::InsufficientTreasuryBalance
+                    );
+                    **treasury_info.try_borrow_mut_lamports()? -= spend.amount;
+                    **recipient.try_borrow_mut_lamports()? += spend.amount;
+                },
+                Some(mint) => {
+                    let (Some(source), Some(destination), Some(token_program)) = (
+                        ctx.accounts.treasury_token_account.as_ref(),
+                        ctx.accounts.recipient_token_account.as_ref(),
+                        ctx.accounts.token_program.as_ref(),
+                    ) else {
+                        return err!(ErrorCode::MissingGovernanceAccount);
+                    };
+                    require!(
+                        source.owner == treasury.key()
+                            && source.mint == mint
+                            && destination.owner == spend.recipient
+                            && destination.mint == mint,
+                        ErrorCode::InvalidTreasurySpend
+                    );
+                    require!(source.amount >= spend.amount, ErrorCode::InsufficientTreasuryBalance);
+                    
+                    token::transfer(
+                        CpiContext::new_with_signer(
+                            token_program.to_account_info(),
+                            Transfer {
+                                from: source.to_account_info(),
+                                to: destination.to_account_info(),
+                                authority: treasury.to_account_info(),
+                            },
+                            &[&[TREASURY_SEED, &[treasury.bump]]],
+                        ),
+                        spend.amount,
+                    )?;
+                },
+            }
+            msg!("Treasury paid {} to {}", spend.amount, spend.recipient);
         },
     }
     
@@ -206,7 +2101,3 @@ pub fn execute_proposal(
     msg!("Proposal {} executed", proposal.proposal_id);
     Ok(())
 }
-
-#[error_code]
-
-
@@ -1,37 +1,76 @@
 ﻿use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
 use crate::ErrorCode;
-use crate::state::{InferenceNode, InferenceResult, Proposal, ProposalStatus};
+use crate::instructions::governance::require_not_paused;
+use crate::instructions::tro::{init_reasoning_node, NODE_SEED, TASK_SEED};
+use crate::state::{
+    InferenceResult, LegacyInferenceNode, ModelCapability, NodeLifecycleStatus, ProgramState,
+    Proposal, ProposalStatus, ReasoningNode, TaskStatus, TroTask, WorkflowClass,
+    CONSENSUS_THRESHOLD_BPS, LEGACY_NODE_SEED, PAUSE_STAKING, PROGRAM_STATE_SEED,
+    RESULT_HASH_MAX_LEN,
+};
+
+const BPS_DENOMINATOR: u64 = 10_000;
 
 #[derive(Accounts)]
-pub struct RegisterNode<'info> {
+pub struct MigrateLegacyNode<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
     
+    /// CHECK: 旧版InferenceNode账户，在指令中校验所有者与数据格式
+    #[account(
+        mut,
+        seeds = [LEGACY_NODE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub legacy_node: UncheckedAccount<'info>,
+    
     #[account(
         init,
         payer = owner,
-        space = 8 + InferenceNode::MAX_SIZE,
-        seeds = [b"node", owner.key().as_ref()],
+        space = 8 + ReasoningNode::MAX_SIZE,
+        seeds = [NODE_SEED, owner.key().as_ref()],
         bump
     )]
-    pub node: Account<'info, InferenceNode>,
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     
     pub system_program: Program<'info, System>,
 }
 
-pub fn register_node(
-    ctx: Context<RegisterNode>,
-    stake_amount: u64,
+/// Moves a node registered through the old `register_node` onto a `ReasoningNode`,
+/// carrying over its reputation and inference count, then closes the old account
+/// and returns its rent to the owner. The legacy stake was never escrowed, so the
+/// node starts active but unstaked, whether or not the legacy node was active,
+/// and must `deposit_stake` before claiming tasks.
+pub fn migrate_legacy_node(
+    ctx: Context<MigrateLegacyNode>,
+    model_capability: ModelCapability,
+    workflow_affinity: WorkflowClass,
 ) -> Result<()> {
-    let node = &mut ctx.accounts.node;
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
     
-    node.owner = ctx.accounts.owner.key();
-    node.stake_amount = stake_amount;
-    node.reputation_score = 50; // 初始信誉评分
-    node.total_inferences = 0;
-    node.is_active = true;
+    let legacy_info = ctx.accounts.legacy_node.to_account_info();
+    require_keys_eq!(*legacy_info.owner, crate::ID, ErrorCode::InvalidLegacyNode);
+    let legacy = LegacyInferenceNode::try_from_account_data(&legacy_info.try_borrow_data()?)?;
+    let owner = ctx.accounts.owner.key();
+    require_keys_eq!(legacy.owner, owner, ErrorCode::InvalidLegacyNode);
     
-    msg!("Node registered: {}", node.owner);
+    // 旧节点的质押只是记账数值、没有托管资金，不予迁移
+    let node = &mut ctx.accounts.reasoning_node;
+    init_reasoning_node(node, owner, owner, model_capability, workflow_affinity, 0, 0)?;
+    node.reputation_score_bps = (legacy.reputation_score.min(100) as u16) * 100;
+    node.total_inferences = legacy.total_inferences;
+    
+    // 关闭旧账户
+    let rent = legacy_info.lamports();
+    **legacy_info.try_borrow_mut_lamports()? = 0;
+    **ctx.accounts.owner.to_account_info().try_borrow_mut_lamports()? += rent;
+    legacy_info.try_borrow_mut_data()?.fill(0);
+    
+    msg!("Legacy node migrated: {}", owner);
     Ok(())
 }
 
@@ -44,10 +83,10 @@ pub struct SubmitInference<'info> {
     
     #[account(
         mut,
-        seeds = [b"node", node.key().as_ref()],
+        seeds = [NODE_SEED, node.key().as_ref()],
         bump
     )]
-    pub node_account: Account<'info, InferenceNode>,
+    pub node_account: Account<'info, ReasoningNode>,
     
     #[account(
         mut,
@@ -72,26 +111,53 @@ pub fn submit_inference(
     ctx: Context<SubmitInference>,
     proposal_id: String,
     result_hash: String,
-    confidence: u8,
+    confidence_bps: u16,
 ) -> Result<()> {
-    require!(ctx.accounts.node_account.is_active, ErrorCode::NodeInactive);
+    require!(
+        ctx.accounts.node_account.status == NodeLifecycleStatus::Active,
+        ErrorCode::NodeInactive
+    );
+    require!(result_hash.len() <= RESULT_HASH_MAX_LEN, ErrorCode::StringTooLong);
+    require!(confidence_bps as u64 <= BPS_DENOMINATOR, ErrorCode::InvalidScore);
+    // 关联了TRO分析任务的提案由任务状态机推进
+    require!(
+        ctx.accounts.proposal.analysis_task.is_none(),
+        ErrorCode::ProposalHasAnalysisTask
+    );
+    require!(
+        matches!(
+            ctx.accounts.proposal.status,
+            ProposalStatus::Submitted | ProposalStatus::Analyzing
+        ),
+        ErrorCode::ProposalNotOpenForInference
+    );
     
     let node_account = &mut ctx.accounts.node_account;
     let inference_result = &mut ctx.accounts.inference_result;
     let clock = Clock::get()?;
     
     // 更新节点统计
-    node_account.total_inferences = node_account.total_inferences.checked_add(1).unwrap();
+    node_account.total_inferences = node_account.total_inferences.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    node_account.last_heartbeat_ts = clock.unix_timestamp;
     
     // 保存推理结果
     inference_result.proposal_id = proposal_id.clone();
+    inference_result.task_id = 0;
     inference_result.node = ctx.accounts.node.key();
+    inference_result.workflow = node_account.workflow_affinity;
+    inference_result.model_capability = node_account.model_capability;
     inference_result.result_hash = result_hash;
+    inference_result.metadata_hash = String::new();
+    inference_result.ipfs_cid = String::new();
     inference_result.timestamp = clock.unix_timestamp;
-    inference_result.confidence = confidence;
+    inference_result.confidence_bps = confidence_bps;
+    inference_result.cache_hit_used = false;
+    inference_result.proof_hash = [0u8; 32];
     
     // 更新提案状态
-    ctx.accounts.proposal.status = ProposalStatus::Analyzing;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.status = ProposalStatus::Analyzing;
+    proposal.inference_count = proposal.inference_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
     
     msg!("Inference submitted for proposal: {}", proposal_id);
     Ok(())
@@ -109,55 +175,132 @@ pub struct AggregateResults<'info> {
         bump
     )]
     pub proposal: Account<'info, Proposal>,
+    // remaining_accounts: 该提案的全部InferenceResult账户
 }
 
+/// Records the aggregator's consensus commitment. Every `InferenceResult` of the
+/// proposal must be passed so the agreement ratio cannot be inflated by leaving
+/// dissenting results out; the contributors root is recomputed over the matching
+/// result PDAs.
 pub fn aggregate_results(
     ctx: Context<AggregateResults>,
     proposal_id: String,
+    consensus_hash: String,
+    contributors_root: [u8; 32],
+    agreement_bps: u16,
 ) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let proposal_key = proposal.key();
+    
     // 检查提案状态
     require!(
-        ctx.accounts.proposal.status == ProposalStatus::Analyzing,
+        proposal.status == ProposalStatus::Analyzing,
         ErrorCode::ProposalNotAnalyzing
     );
+    require!(proposal.analysis_task.is_none(), ErrorCode::ProposalHasAnalysisTask);
+    require!(consensus_hash.len() <= RESULT_HASH_MAX_LEN, ErrorCode::StringTooLong);
+    require!(
+        !ctx.remaining_accounts.is_empty()
+            && ctx.remaining_accounts.len() == proposal.inference_count as usize,
+        ErrorCode::InvalidConsensus
+    );
+    
+    let mut seen: Vec<Pubkey> = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut leaves: Vec<[u8; 32]> = Vec::new();
+    for info in ctx.remaining_accounts {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidConsensus);
+        let result = InferenceResult::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[b"inference", proposal_key.as_ref(), result.node.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidConsensus);
+        require!(!seen.contains(&info.key()), ErrorCode::InvalidConsensus);
+        seen.push(info.key());
+        
+        if result.result_hash == consensus_hash {
+            leaves.push(hashv(&[info.key.as_ref()]).to_bytes());
+        }
+    }
     
-    // 注意：在Solana中，我们无法在链上直接查询所有推理结果
-    // 聚合逻辑应该由后端服务完成，这里只更新状态
-    // 后端会查询所有InferenceResult账户，进行聚合，然后调用此函数更新状态
+    // 一致率 = 与共识哈希一致的结果数 / 全部结果数
+    let computed_bps = (leaves.len() as u64 * BPS_DENOMINATOR / seen.len() as u64) as u16;
+    require!(agreement_bps == computed_bps, ErrorCode::InvalidConsensus);
+    // 至少三分之二的结果与共识一致
+    require!(computed_bps >= CONSENSUS_THRESHOLD_BPS, ErrorCode::ConsensusNotReached);
     
-    ctx.accounts.proposal.status = ProposalStatus::Completed;
+    leaves.sort_unstable();
+    require!(merkle_root(leaves) == contributors_root, ErrorCode::InvalidConsensus);
     
-    msg!("Results aggregated for proposal: {}", proposal_id);
+    proposal.consensus_hash = consensus_hash;
+    proposal.contributors_root = contributors_root;
+    proposal.agreement_bps = agreement_bps;
+    proposal.status = ProposalStatus::Completed;
+    
+    msg!(
+        "Results aggregated for proposal: {} ({}/{} agree)",
+        proposal_id,
+        computed_bps,
+        BPS_DENOMINATOR
+    );
     Ok(())
 }
 
+/// Merkle root over sorted leaves (sha256 of each contributing result PDA). Pairs
+/// are hashed as sha256(left || right); an unpaired node is carried up unchanged.
+fn merkle_root(mut level: Vec<[u8; 32]>) -> [u8; 32] {
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => hashv(&[left, right]).to_bytes(),
+                _ => pair[0],
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or([0u8; 32])
+}
+
 #[derive(Accounts)]
-#[instruction(node_address: Pubkey)]
+#[instruction(node_address: Pubkey, task_id: u64)]
 pub struct RateNode<'info> {
     pub rater: Signer<'info>,
     
     #[account(
         mut,
-        seeds = [b"node", node_address.as_ref()],
+        seeds = [NODE_SEED, node_address.as_ref()],
         bump
     )]
-    pub node: Account<'info, InferenceNode>,
+    pub node: Account<'info, ReasoningNode>,
+    
+    // 只有该节点完成的任务的提交者可以评分，每个任务只能评一次
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump,
+        constraint = task.submitter == rater.key() @ ErrorCode::RatingNotAllowed,
+        constraint = task.result_node == node_address @ ErrorCode::RatingNotAllowed
+    )]
+    pub task: Account<'info, TroTask>,
 }
 
 pub fn rate_node(
     ctx: Context<RateNode>,
     _node_address: Pubkey,
+    _task_id: u64,
     score: u8,
 ) -> Result<()> {
     require!(score <= 100, ErrorCode::InvalidScore);
     
-    let node = &mut ctx.accounts.node;
+    let task = &mut ctx.accounts.task;
+    require!(task.status == TaskStatus::Finalized, ErrorCode::RatingNotAllowed);
+    require!(!task.rated, ErrorCode::AlreadyRated);
+    task.rated = true;
     
-    // 更新信誉评分（简单平均，实际应该用加权平均）
-    let new_score = ((node.reputation_score as u16 + score as u16) / 2) as u8;
-    node.reputation_score = new_score;
+    // 评分0-100换算为基点，计入信誉的指数移动平均
+    let node = &mut ctx.accounts.node;
+    node.record_rating(score as u16 * 100);
     
-    msg!("Node rated: {} (new score: {})", score, new_score);
+    msg!("Node rated: {} (new score: {} bps)", score, node.reputation_score_bps);
     Ok(())
 }
-
@@ -1,16 +1,17 @@
+pub mod council;
 pub mod data_contribution;
 pub mod governance;
 pub mod inference_network;
 pub mod rewards;
 pub mod training;
+pub mod treasury;
 pub mod tro;
 
-pub use data_contribution::SubmitProposal;
-pub use governance::{CreateGovernanceProposal, ExecuteProposal, VoteOnProposal};
-pub use inference_network::{AggregateResults, RateNode, RegisterNode, SubmitInference};
-pub use rewards::{ClaimReward, DistributeInferenceReward, DistributeRewards, RewardType};
-pub use training::{CreateTrainingTask, SubmitGradient};
-pub use tro::{
-    ChallengeTaskResult, ClaimTask, FinalizeTask, RegisterReasoningNode, ResolveChallenge,
-    SlashMaliciousNode, SubmitIntentTask, SubmitProof, SubmitReasoning, SubmitVerification,
-};
+pub use council::*;
+pub use data_contribution::*;
+pub use governance::*;
+pub use inference_network::*;
+pub use rewards::*;
+pub use training::*;
+pub use treasury::*;
+pub use tro::*;
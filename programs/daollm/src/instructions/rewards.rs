@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
-use crate::state::InferenceNode;
+use crate::instructions::governance::require_not_paused;
+use crate::instructions::tro::NODE_SEED;
+use crate::state::{
+    NodeLifecycleStatus, ProgramState, ReasoningNode, PAUSE_REWARDS, PROGRAM_STATE_SEED,
+};
 
 #[derive(Accounts)]
 pub struct DistributeRewards<'info> {
@@ -10,6 +14,12 @@ pub struct DistributeRewards<'info> {
     #[account(mut)]
     pub recipient: AccountInfo<'info>,
     
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -24,10 +34,16 @@ pub struct DistributeInferenceReward<'info> {
     
     #[account(
         mut,
-        seeds = [b"node", recipient.key().as_ref()],
+        seeds = [NODE_SEED, recipient.key().as_ref()],
         bump
     )]
-    pub node: Account<'info, InferenceNode>,
+    pub node: Account<'info, ReasoningNode>,
+    
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
     
     pub system_program: Program<'info, System>,
 }
@@ -40,6 +56,12 @@ pub struct ClaimReward<'info> {
     #[account(mut)]
     pub reward_account: AccountInfo<'info>,
     
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -47,6 +69,7 @@ pub fn distribute_data_contribution_reward(
     ctx: Context<DistributeRewards>,
     amount: u64,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_REWARDS)?;
     require!(amount > 0, RewardError::InvalidAmount);
     
     // 转账SOL lamports给接收者
@@ -61,19 +84,23 @@ pub fn distribute_inference_reward(
     ctx: Context<DistributeInferenceReward>,
     amount: u64,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_REWARDS)?;
     require!(amount > 0, RewardError::InvalidAmount);
-    require!(ctx.accounts.node.is_active, RewardError::NodeInactive);
+    require!(
+        ctx.accounts.node.status == NodeLifecycleStatus::Active,
+        RewardError::NodeInactive
+    );
     
     // 根据节点信誉计算实际奖励（信誉越高奖励越多）
-    let reputation_multiplier = ctx.accounts.node.reputation_score as u64;
-    let adjusted_amount = (amount * reputation_multiplier) / 100;
+    let reputation_multiplier = ctx.accounts.node.reputation_score_bps as u128;
+    let adjusted_amount = ((amount as u128) * reputation_multiplier / 10_000) as u64;
     
     // 转账SOL lamports给节点所有者
     **ctx.accounts.distributor.to_account_info().try_borrow_mut_lamports()? -= adjusted_amount;
     **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += adjusted_amount;
     
-    msg!("Distributed {} lamports (adjusted from {} based on reputation {}) to inference node", 
-         adjusted_amount, amount, ctx.accounts.node.reputation_score);
+    msg!("Distributed {} lamports (adjusted from {} based on reputation {} bps) to inference node", 
+         adjusted_amount, amount, ctx.accounts.node.reputation_score_bps);
     Ok(())
 }
 
@@ -82,6 +109,7 @@ pub fn claim_reward(
     reward_type: RewardType,
     amount: u64,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_REWARDS)?;
     require!(amount > 0, RewardError::InvalidAmount);
     
     // 验证奖励账户有足够的余额
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
+use crate::instructions::governance::require_not_paused;
 use crate::state::training::*;
+use crate::state::{ProgramState, PAUSE_TASKS, PROGRAM_STATE_SEED};
 
 #[derive(Accounts)]
 #[instruction(task_id: u64)]
@@ -16,6 +18,12 @@ pub struct CreateTrainingTask<'info> {
     )]
     pub task: Account<'info, TrainingTask>,
     
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -42,6 +50,12 @@ pub struct SubmitGradient<'info> {
     )]
     pub gradient: Account<'info, GradientSubmission>,
     
+    #[account(
+        seeds = [PROGRAM_STATE_SEED],
+        bump = program_state.bump
+    )]
+    pub program_state: Account<'info, ProgramState>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -51,6 +65,8 @@ pub fn create_training_task(
     model_config_hash: String,
     total_nodes: u32,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+    
     let task = &mut ctx.accounts.task;
     let clock = Clock::get()?;
     
@@ -73,6 +89,8 @@ pub fn submit_gradient(
     task_id: u64,
     gradient_hash: String,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+    
     let task = &mut ctx.accounts.task;
     let gradient = &mut ctx.accounts.gradient;
     let clock = Clock::get()?;
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    instructions::{
+        governance::GOVERNANCE_CONFIG_SEED,
+        tro::{ECONOMY_SEED, REWARD_VAULT_SEED},
+    },
+    state::{
+        EconomyConfig, GovernanceConfig, RewardVault, SpendLimit, Treasury,
+        GOVERNANCE_AUTHORITY_SEED, MAX_SPEND_LIMITS, TREASURY_SEED,
+    },
+    ErrorCode,
+};
+
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        seeds = [GOVERNANCE_CONFIG_SEED],
+        bump = governance_config.bump,
+        has_one = authority
+    )]
+    pub governance_config: Account<'info, GovernanceConfig>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Treasury::MAX_SIZE,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.spend_limits = Vec::new();
+    treasury.total_swept = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized");
+    Ok(())
+}
+
+/// Limits are only changed through a governance proposal, which signs as the
+/// governance PDA.
+#[derive(Accounts)]
+pub struct SetTreasurySpendLimit<'info> {
+    #[account(seeds = [GOVERNANCE_AUTHORITY_SEED], bump)]
+    pub governance_authority: Signer<'info>,
+    #[account(mut, seeds = [TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn set_treasury_spend_limit(
+    ctx: Context<SetTreasurySpendLimit>,
+    mint: Option<Pubkey>,
+    max_per_period: u64,
+    period_secs: i64,
+) -> Result<()> {
+    require!(period_secs > 0, ErrorCode::InvalidSpendLimit);
+
+    let treasury = &mut ctx.accounts.treasury;
+    // 本周期已支出的金额仍计入调低后的额度
+    match treasury
+        .spend_limits
+        .iter_mut()
+        .find(|limit| limit.mint == mint)
+    {
+        Some(limit) => {
+            limit.max_per_period = max_per_period;
+            limit.period_secs = period_secs;
+        }
+        None => {
+            require!(
+                treasury.spend_limits.len() < MAX_SPEND_LIMITS,
+                ErrorCode::InvalidSpendLimit
+            );
+            treasury.spend_limits.push(SpendLimit {
+                mint,
+                max_per_period,
+                period_secs,
+                period_start: Clock::get()?.unix_timestamp,
+                spent_in_period: 0,
+            });
+        }
+    }
+
+    msg!(
+        "Treasury spend limit for {:?} set to {} per {} seconds",
+        mint,
+        max_per_period,
+        period_secs
+    );
+    Ok(())
+}
+
+/// Moves slashed stake accumulated in `EconomyConfig::slash_pool` from the reward
+/// vault into the treasury. Anyone may crank it.
+#[derive(Accounts)]
+pub struct SweepSlashPool<'info> {
+    #[account(mut, seeds = [ECONOMY_SEED], bump)]
+    pub economy_config: Account<'info, EconomyConfig>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED],
+        bump,
+        constraint = reward_vault.key() == economy_config.reward_vault
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    #[account(mut, seeds = [TREASURY_SEED], bump = treasury.bump)]
+    pub treasury: Account<'info, Treasury>,
+}
+
+pub fn sweep_slash_pool(ctx: Context<SweepSlashPool>) -> Result<()> {
+    let economy = &mut ctx.accounts.economy_config;
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    let treasury = &mut ctx.accounts.treasury;
+
+    // 奖励金库保留免租金余额
+    let vault_info = reward_vault.to_account_info();
+    let reserve = Rent::get()?.minimum_balance(vault_info.data_len());
+    let amount = economy
+        .slash_pool
+        .min(vault_info.lamports().saturating_sub(reserve));
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    **vault_info.try_borrow_mut_lamports()? -= amount;
+    **treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    economy.slash_pool -= amount;
+    reward_vault.total_accrued = reward_vault.total_accrued.saturating_sub(amount);
+    treasury.total_swept = treasury.total_swept.saturating_add(amount);
+
+    msg!("Swept {} lamports of slashed stake into the treasury", amount);
+    Ok(())
+}
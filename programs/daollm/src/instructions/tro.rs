@@ -1,27 +1,35 @@
 use anchor_lang::{prelude::*, system_program};
+use solana_sha256_hasher::hashv;
 
 use crate::{
+    instructions::{council::consume_council_action, governance::require_not_paused},
     state::{
-        ChallengeRecord, ChallengeStatus, EconomyConfig, InferenceResult, KnowledgeGraphState,
-        ModelCapability, NodeLifecycleStatus, ProofPolicy, ProofRegistry, ReasoningNode,
-        ResolutionOutcome, RewardVault, StakeVault, TaskCriticality, TaskStatus, TaskType, TroTask,
-        WorkflowClass, CID_MAX_LEN, EVIDENCE_MAX_LEN, HASH_MAX_LEN, INTENT_MAX_LEN, REASON_MAX_LEN,
+        ChallengeRecord, ChallengeStatus, Council, CouncilAction, CouncilActionKind,
+        EconomyConfig, InferenceResult, KnowledgeGraphState, ModelCapability,
+        NodeLifecycleStatus, ProgramState, ProofPolicy, ProofRegistry, Proposal, ProposalStatus,
+        ReasoningCommit, ReasoningNode, ResolutionOutcome, RewardVault, StakeVault, TaskCriticality, TaskStatus, TaskType, TroTask,
+        WorkflowClass, CID_MAX_LEN, COUNCIL_SEED, EVIDENCE_MAX_LEN, HASH_MAX_LEN, INTENT_MAX_LEN,
+        PAUSE_REWARDS, PAUSE_STAKING, PAUSE_TASKS, PROGRAM_STATE_SEED, REASON_MAX_LEN,
     },
     ErrorCode,
 };
 
-const TASK_SEED: &[u8] = b"tro-task";
-const NODE_SEED: &[u8] = b"reasoning-node";
+pub(crate) const TASK_SEED: &[u8] = b"tro-task";
+pub(crate) const NODE_SEED: &[u8] = b"reasoning-node";
 const INFERENCE_SEED: &[u8] = b"inference-result";
 const KNOWLEDGE_GRAPH_SEED: &[u8] = b"kg-state";
 const PROOF_SEED: &[u8] = b"proof-registry";
 const CHALLENGE_SEED: &[u8] = b"challenge";
-const ECONOMY_SEED: &[u8] = b"economy-config";
-const REWARD_VAULT_SEED: &[u8] = b"reward-vault";
+pub(crate) const ECONOMY_SEED: &[u8] = b"economy-config";
+pub(crate) const REWARD_VAULT_SEED: &[u8] = b"reward-vault";
 const STAKE_VAULT_SEED: &[u8] = b"stake-vault";
+const COMMIT_SEED: &[u8] = b"reasoning-commit";
 const MIN_CHALLENGE_WINDOW: i64 = 1_800; // 30 minutes
 const MAX_CHALLENGE_WINDOW: i64 = 7 * 24 * 3_600; // 7 days
 const BPS_DENOMINATOR: u32 = 10_000;
+const COMMIT_WINDOW_SECS: i64 = 600;
+const REVEAL_WINDOW_SECS: i64 = 600;
+const REVEAL_BOND_LAMPORTS: u64 = 10_000_000;
 
 #[derive(Accounts)]
 #[instruction(task_id: u64)]
@@ -36,12 +44,50 @@ pub struct SubmitIntentTask<'info> {
         bump
     )]
     pub task: Account<'info, TroTask>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn submit_intent_task(
     ctx: Context<SubmitIntentTask>,
     task_id: u64,
+    intent: String,
+    task_type: TaskType,
+    requested_workflow: WorkflowClass,
+    criticality: TaskCriticality,
+    complexity_score: u16,
+    stake_pool: u64,
+    min_node_stake: u64,
+    metadata_hash: String,
+    challenge_window_secs: i64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
+    open_task(
+        &mut ctx.accounts.task,
+        task_id,
+        ctx.accounts.submitter.key(),
+        intent,
+        task_type,
+        requested_workflow,
+        criticality,
+        complexity_score,
+        stake_pool,
+        min_node_stake,
+        metadata_hash,
+        challenge_window_secs,
+    )
+}
+
+/// Initializes a freshly created task; shared by `submit_intent_task` and proposals
+/// that open a governance analysis task.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn open_task(
+    task: &mut TroTask,
+    task_id: u64,
+    submitter: Pubkey,
     mut intent: String,
     task_type: TaskType,
     requested_workflow: WorkflowClass,
@@ -55,13 +101,10 @@ pub fn submit_intent_task(
     enforce_len(&intent, INTENT_MAX_LEN)?;
     enforce_len(&metadata_hash, HASH_MAX_LEN)?;
     require!(
-        challenge_window_secs >= MIN_CHALLENGE_WINDOW
-            && challenge_window_secs <= MAX_CHALLENGE_WINDOW,
+        (MIN_CHALLENGE_WINDOW..=MAX_CHALLENGE_WINDOW).contains(&challenge_window_secs),
         ErrorCode::InvalidChallengeWindow
     );
 
-    let submitter = ctx.accounts.submitter.key();
-    let task = &mut ctx.accounts.task;
     let clock = Clock::get()?;
 
     if intent.is_empty() {
@@ -101,10 +144,68 @@ pub fn submit_intent_task(
     task.updated_ts = clock.unix_timestamp;
     task.last_actor = submitter;
     task.dispute_count = 0;
+    task.proposal = None;
+    task.stake_escrowed = false;
+    task.result_node = Pubkey::default();
+    task.claimed_ts = 0;
+    task.reasoned_ts = 0;
+    task.rated = false;
+    task.verified = false;
+    // Multi-node tasks seal results so nodes cannot copy each other's hashes.
+    task.commit_reveal = workflow == WorkflowClass::ConsensusGuarded;
+    task.commit_deadline = 0;
+    task.reveal_deadline = 0;
+    task.commit_count = 0;
+    task.reveal_count = 0;
+    task.required_nodes = workflow.required_nodes();
+    task.assigned_nodes = Vec::new();
+    task.dissenters = Vec::new();
+    task.round = 0;
+    task.agreeing_nodes = Vec::new();
 
     Ok(())
 }
 
+/// Mirrors task progress onto the DAO proposal the task was opened for, if any.
+fn sync_linked_proposal(
+    task: &TroTask,
+    proposal: Option<&mut Account<Proposal>>,
+    status: ProposalStatus,
+) -> Result<()> {
+    let Some(linked) = task.proposal else {
+        return Ok(());
+    };
+    let proposal = proposal.ok_or(ErrorCode::InvalidProposalLink)?;
+    require_keys_eq!(proposal.key(), linked, ErrorCode::InvalidProposalLink);
+    proposal.status = status;
+    Ok(())
+}
+
+/// Locks the stake of a node that just submitted a result. Verification can
+/// restart the challenge window later, so this covers the longest one.
+fn hold_stake_for_challenge(node: &mut ReasoningNode, now: i64) -> Result<()> {
+    let until = now
+        .checked_add(MAX_CHALLENGE_WINDOW)
+        .ok_or(ErrorCode::MathOverflow)?;
+    node.extend_challenge_exposure(until);
+    Ok(())
+}
+
+/// Reopens the task for a fresh set of nodes. Bumping the round keeps new
+/// commits and results from colliding with the accounts of earlier rounds.
+fn start_new_round(task: &mut TroTask) -> Result<()> {
+    task.status = TaskStatus::Pending;
+    task.round = task.round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    task.assigned_nodes.clear();
+    task.agreeing_nodes.clear();
+    task.dissenters.clear();
+    task.commit_deadline = 0;
+    task.reveal_deadline = 0;
+    task.commit_count = 0;
+    task.reveal_count = 0;
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct RegisterReasoningNode<'info> {
     #[account(mut)]
@@ -117,9 +218,20 @@ pub struct RegisterReasoningNode<'info> {
         bump
     )]
     pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + StakeVault::MAX_SIZE,
+        seeds = [STAKE_VAULT_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: Account<'info, StakeVault>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
+/// The initial stake is escrowed in the node's stake vault like any later deposit.
 pub fn register_reasoning_node(
     ctx: Context<RegisterReasoningNode>,
     controller: Pubkey,
@@ -128,16 +240,58 @@ pub fn register_reasoning_node(
     base_stake_requirement: u64,
     initial_stake: u64,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
     require!(
         initial_stake >= base_stake_requirement,
         ErrorCode::InsufficientStake
     );
 
+    if initial_stake > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.stake_vault.to_account_info(),
+                },
+            ),
+            initial_stake,
+        )?;
+    }
+
+    let stake_vault = &mut ctx.accounts.stake_vault;
+    stake_vault.owner = ctx.accounts.owner.key();
+    stake_vault.total_stake = initial_stake;
+    stake_vault.bump = ctx.bumps.stake_vault;
+
     let node = &mut ctx.accounts.reasoning_node;
-    let owner_key = ctx.accounts.owner.key();
+    init_reasoning_node(
+        node,
+        ctx.accounts.owner.key(),
+        controller,
+        model_capability,
+        workflow_affinity,
+        base_stake_requirement,
+        initial_stake,
+    )?;
+    node.stake_vault_bump = ctx.bumps.stake_vault;
+
+    Ok(())
+}
+
+/// Writes a freshly registered node; shared with the legacy node migration.
+pub(crate) fn init_reasoning_node(
+    node: &mut ReasoningNode,
+    owner: Pubkey,
+    controller: Pubkey,
+    model_capability: ModelCapability,
+    workflow_affinity: WorkflowClass,
+    base_stake_requirement: u64,
+    initial_stake: u64,
+) -> Result<()> {
     let clock = Clock::get()?;
 
-    node.owner = owner_key;
+    node.owner = owner;
     node.controller = controller;
     node.model_capability = model_capability;
     node.workflow_affinity = workflow_affinity;
@@ -162,6 +316,11 @@ pub fn register_reasoning_node(
     node.dynamic_multiplier_bps = BPS_DENOMINATOR as u16;
     node.last_settlement_ts = clock.unix_timestamp;
     node.stake_vault_bump = 0;
+    node.unbonding_amount = 0;
+    node.unbond_available_ts = 0;
+    node.challenge_exposure_until = 0;
+    node.open_challenges = 0;
+    node.claimed_ts = 0;
 
     Ok(())
 }
@@ -183,9 +342,15 @@ pub struct ClaimTask<'info> {
         bump
     )]
     pub task: Account<'info, TroTask>,
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
 pub fn claim_task(ctx: Context<ClaimTask>, task_id: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
     let node = &mut ctx.accounts.reasoning_node;
     let task = &mut ctx.accounts.task;
     let clock = Clock::get()?;
@@ -206,13 +371,192 @@ pub fn claim_task(ctx: Context<ClaimTask>, task_id: u64) -> Result<()> {
         node.active_task_id == 0 || node.active_task_id == task_id,
         ErrorCode::NodeBusy
     );
+    if task.assigned_nodes.contains(&node.owner) {
+        // Nodes that already submitted for this round cannot claim it again.
+        require!(node.active_task_id == task_id, ErrorCode::TaskNotClaimable);
+    } else {
+        require!(
+            task.assigned_nodes.len() < task.required_nodes as usize,
+            ErrorCode::TaskFullyAssigned
+        );
+        task.assigned_nodes.push(node.owner);
+    }
+
+    if task.commit_reveal {
+        if task.commit_deadline == 0 {
+            task.commit_deadline = clock
+                .unix_timestamp
+                .checked_add(COMMIT_WINDOW_SECS)
+                .ok_or(ErrorCode::MathOverflow)?;
+            task.reveal_deadline = task
+                .commit_deadline
+                .checked_add(REVEAL_WINDOW_SECS)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+        require!(
+            clock.unix_timestamp <= task.commit_deadline,
+            ErrorCode::CommitWindowClosed
+        );
+    }
 
+    if node.active_task_id != task_id {
+        node.claimed_ts = clock.unix_timestamp;
+    }
     node.active_task_id = task_id;
     node.last_heartbeat_ts = clock.unix_timestamp;
     task.status = TaskStatus::Reasoning;
     task.workflow = resolve_workflow(task.workflow, task.criticality, task.complexity_score);
     task.last_actor = node.owner;
     task.updated_ts = clock.unix_timestamp;
+    task.claimed_ts = clock.unix_timestamp;
+    sync_linked_proposal(task, ctx.accounts.proposal.as_mut(), ProposalStatus::Analyzing)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(node_owner: Pubkey)]
+pub struct Heartbeat<'info> {
+    pub operator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.as_ref()],
+        bump,
+        constraint = operator.key() == reasoning_node.owner
+            || operator.key() == reasoning_node.controller @ ErrorCode::UnauthorizedActor
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+}
+
+/// Signed by the node owner or its controller. Not gated by the pause flags so
+/// nodes are not treated as offline while tasks are paused.
+pub fn heartbeat(ctx: Context<Heartbeat>, _node_owner: Pubkey) -> Result<()> {
+    ctx.accounts.reasoning_node.last_heartbeat_ts = Clock::get()?.unix_timestamp;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64, node_owner: Pubkey)]
+pub struct ReclaimStalledTask<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TroTask>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    /// CHECK: must be empty; a node that committed is handled by the reveal flow
+    #[account(
+        seeds = [COMMIT_SEED, &task_id.to_le_bytes(), &[task.round], node_owner.as_ref()],
+        bump
+    )]
+    pub commit: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, node_owner.as_ref()],
+        bump = reasoning_node.stake_vault_bump,
+        constraint = stake_vault.owner == node_owner
+    )]
+    pub stake_vault: Option<Account<'info, StakeVault>>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED],
+        bump,
+        constraint = reward_vault.authority == economy_config.authority
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
+    #[account(
+        mut,
+        seeds = [ECONOMY_SEED],
+        bump
+    )]
+    pub economy_config: Account<'info, EconomyConfig>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Permissionless crank for a claim that ran past the configured task timeout,
+/// whether or not the node kept heartbeating. The node is unassigned, the task
+/// and any linked proposal reopen once no node is left on it, and time offline
+/// beyond the grace period is slashed from the stake vault. A node still bound
+/// to a task that no longer holds it, e.g. after the task moved to a new round,
+/// is simply released.
+pub fn reclaim_stalled_task(
+    ctx: Context<ReclaimStalledTask>,
+    task_id: u64,
+    node_owner: Pubkey,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
+    let task = &mut ctx.accounts.task;
+    let node = &mut ctx.accounts.reasoning_node;
+    let clock = Clock::get()?;
+
+    require!(node.active_task_id == task_id, ErrorCode::NodeNotAssigned);
+    if task.status != TaskStatus::Reasoning || !task.assigned_nodes.contains(&node_owner) {
+        node.active_task_id = 0;
+        return Ok(());
+    }
+    let economy = &ctx.accounts.economy_config;
+    let (offline_grace_secs, penalty_bps_per_hour) =
+        (economy.offline_grace_secs, economy.offline_penalty_bps_per_hour);
+    let claim_deadline = node.claimed_ts.saturating_add(economy.task_timeout_secs);
+    require!(clock.unix_timestamp > claim_deadline, ErrorCode::ClaimNotExpired);
+    require!(
+        ctx.accounts.commit.data_is_empty(),
+        ErrorCode::NodeAlreadyCommitted
+    );
+
+    task.assigned_nodes.retain(|assigned| *assigned != node_owner);
+    if task.assigned_nodes.is_empty() {
+        task.status = TaskStatus::Pending;
+        if task.commit_count == 0 {
+            task.commit_deadline = 0;
+            task.reveal_deadline = 0;
+        }
+        sync_linked_proposal(task, ctx.accounts.proposal.as_mut(), ProposalStatus::Submitted)?;
+    }
+    task.updated_ts = clock.unix_timestamp;
+    task.last_actor = ctx.accounts.caller.key();
+    node.active_task_id = 0;
+
+    // Stake is slashed per full hour offline beyond the grace period.
+    let offline_secs = clock.unix_timestamp.saturating_sub(node.last_heartbeat_ts);
+    let penalized_hours = offline_secs.saturating_sub(offline_grace_secs) / 3_600;
+    if penalized_hours == 0 {
+        return Ok(());
+    }
+    let Some(stake_vault) = ctx.accounts.stake_vault.as_mut() else {
+        require!(node.stake_vault_bump == 0, ErrorCode::MissingStakeVault);
+        return Ok(());
+    };
+    let penalty = (node.stake_amount as u128)
+        .saturating_mul(penalty_bps_per_hour as u128)
+        .saturating_mul(penalized_hours as u128)
+        / BPS_DENOMINATOR as u128;
+    let penalty = (penalty.min(u64::MAX as u128) as u64).min(withdrawable_stake(stake_vault)?);
+    if penalty == 0 {
+        return Ok(());
+    }
+
+    node.apply_slash(penalty);
+    stake_vault.total_stake = stake_vault.total_stake.saturating_sub(penalty);
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    **stake_vault.to_account_info().try_borrow_mut_lamports()? -= penalty;
+    **reward_vault
+        .to_account_info()
+        .try_borrow_mut_lamports()? += penalty;
+    reward_vault.total_accrued = reward_vault.total_accrued.saturating_add(penalty);
+    let economy = &mut ctx.accounts.economy_config;
+    economy.slash_pool = economy.slash_pool.saturating_add(penalty);
 
     Ok(())
 }
@@ -233,69 +577,506 @@ pub struct SubmitReasoning<'info> {
         seeds = [TASK_SEED, &task_id.to_le_bytes()],
         bump
     )]
-    pub task: Account<'info, TroTask>,
+    pub task: Account<'info, TroTask>,
+    #[account(
+        init,
+        payer = node_owner,
+        space = 8 + InferenceResult::MAX_SIZE,
+        seeds = [
+            INFERENCE_SEED,
+            &task_id.to_le_bytes(),
+            node_owner.key().as_ref(),
+            &[task.round]
+        ],
+        bump
+    )]
+    pub inference_result: Account<'info, InferenceResult>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn submit_reasoning(
+    ctx: Context<SubmitReasoning>,
+    task_id: u64,
+    result_hash: String,
+    metadata_hash: String,
+    ipfs_cid: String,
+    confidence_bps: u16,
+    cache_hit_used: bool,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+    enforce_len(&result_hash, HASH_MAX_LEN)?;
+    enforce_len(&metadata_hash, HASH_MAX_LEN)?;
+    enforce_len(&ipfs_cid, CID_MAX_LEN)?;
+
+    let node = &mut ctx.accounts.reasoning_node;
+    let task = &mut ctx.accounts.task;
+    let inference = &mut ctx.accounts.inference_result;
+    let clock = Clock::get()?;
+
+    require!(
+        task.status == TaskStatus::Reasoning,
+        ErrorCode::TaskNotInReasoning
+    );
+    require!(!task.commit_reveal, ErrorCode::CommitRevealRequired);
+    require!(
+        node.active_task_id == task_id && task.assigned_nodes.contains(&node.owner),
+        ErrorCode::NodeNotAssigned
+    );
+
+    node.total_inferences = node.total_inferences.saturating_add(1);
+    node.last_heartbeat_ts = clock.unix_timestamp;
+    node.active_task_id = 0;
+    hold_stake_for_challenge(node, clock.unix_timestamp)?;
+
+    task.reasoning_result = result_hash.clone();
+    task.metadata_hash = metadata_hash.clone();
+    task.ipfs_result = ipfs_cid.clone();
+    task.cache_hit_used = cache_hit_used;
+    task.status = TaskStatus::Verifying;
+    task.last_actor = node.owner;
+    task.updated_ts = clock.unix_timestamp;
+    task.result_node = node.owner;
+    task.reasoned_ts = clock.unix_timestamp;
+
+    inference.proposal_id = format!("intent-{}", task_id);
+    inference.task_id = task_id;
+    inference.node = node.owner;
+    inference.workflow = task.workflow;
+    inference.model_capability = node.model_capability;
+    inference.result_hash = result_hash;
+    inference.metadata_hash = metadata_hash;
+    inference.ipfs_cid = ipfs_cid;
+    inference.timestamp = clock.unix_timestamp;
+    inference.confidence_bps = confidence_bps;
+    inference.cache_hit_used = cache_hit_used;
+    inference.proof_hash = [0u8; 32];
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct CommitReasoning<'info> {
+    #[account(mut)]
+    pub node_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.key().as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TroTask>,
+    #[account(
+        init,
+        payer = node_owner,
+        space = 8 + ReasoningCommit::MAX_SIZE,
+        seeds = [COMMIT_SEED, &task_id.to_le_bytes(), &[task.round], node_owner.key().as_ref()],
+        bump
+    )]
+    pub commit: Account<'info, ReasoningCommit>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Seals a node's result on a commit-reveal task and escrows the reveal bond in
+/// the commit account.
+pub fn commit_reasoning(
+    ctx: Context<CommitReasoning>,
+    task_id: u64,
+    commitment: [u8; 32],
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
+    let node = &mut ctx.accounts.reasoning_node;
+    let task = &mut ctx.accounts.task;
+    let clock = Clock::get()?;
+
+    require!(task.commit_reveal, ErrorCode::CommitRevealNotEnabled);
+    require!(
+        task.status == TaskStatus::Reasoning,
+        ErrorCode::TaskNotInReasoning
+    );
+    require!(
+        node.active_task_id == task_id && task.assigned_nodes.contains(&node.owner),
+        ErrorCode::NodeNotAssigned
+    );
+    require!(
+        clock.unix_timestamp <= task.commit_deadline,
+        ErrorCode::CommitWindowClosed
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.node_owner.to_account_info(),
+                to: ctx.accounts.commit.to_account_info(),
+            },
+        ),
+        REVEAL_BOND_LAMPORTS,
+    )?;
+
+    let commit = &mut ctx.accounts.commit;
+    commit.task_id = task_id;
+    commit.round = task.round;
+    commit.node = node.owner;
+    commit.commitment = commitment;
+    commit.bond = REVEAL_BOND_LAMPORTS;
+    commit.committed_at = clock.unix_timestamp;
+    commit.revealed = false;
+    commit.revealed_at = 0;
+    commit.result_hash = String::new();
+
+    task.commit_count = task
+        .commit_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    task.updated_ts = clock.unix_timestamp;
+    node.last_heartbeat_ts = clock.unix_timestamp;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct RevealReasoning<'info> {
+    #[account(mut)]
+    pub node_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.key().as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TroTask>,
+    #[account(
+        mut,
+        seeds = [COMMIT_SEED, &task_id.to_le_bytes(), &[task.round], node_owner.key().as_ref()],
+        bump
+    )]
+    pub commit: Account<'info, ReasoningCommit>,
+    #[account(
+        init,
+        payer = node_owner,
+        space = 8 + InferenceResult::MAX_SIZE,
+        seeds = [
+            INFERENCE_SEED,
+            &task_id.to_le_bytes(),
+            node_owner.key().as_ref(),
+            &[task.round]
+        ],
+        bump
+    )]
+    pub inference_result: Account<'info, InferenceResult>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a sealed result once the commit window has closed and returns the bond.
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_reasoning(
+    ctx: Context<RevealReasoning>,
+    task_id: u64,
+    result_hash: String,
+    salt: [u8; 32],
+    metadata_hash: String,
+    ipfs_cid: String,
+    confidence_bps: u16,
+    cache_hit_used: bool,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+    enforce_len(&result_hash, HASH_MAX_LEN)?;
+    enforce_len(&metadata_hash, HASH_MAX_LEN)?;
+    enforce_len(&ipfs_cid, CID_MAX_LEN)?;
+
+    let node = &mut ctx.accounts.reasoning_node;
+    let task = &mut ctx.accounts.task;
+    let commit = &mut ctx.accounts.commit;
+    let inference = &mut ctx.accounts.inference_result;
+    let clock = Clock::get()?;
+
+    require!(task.commit_reveal, ErrorCode::CommitRevealNotEnabled);
+    require!(
+        task.status == TaskStatus::Reasoning,
+        ErrorCode::TaskNotInReasoning
+    );
+    require!(
+        clock.unix_timestamp > task.commit_deadline,
+        ErrorCode::RevealWindowNotOpen
+    );
+    require!(
+        clock.unix_timestamp <= task.reveal_deadline,
+        ErrorCode::RevealWindowClosed
+    );
+    require!(!commit.revealed, ErrorCode::AlreadyRevealed);
+    let expected = hashv(&[result_hash.as_bytes(), &salt, node.owner.as_ref()]).to_bytes();
+    require!(expected == commit.commitment, ErrorCode::InvalidReveal);
+
+    let bond = commit.bond;
+    **commit.to_account_info().try_borrow_mut_lamports()? -= bond;
+    **ctx
+        .accounts
+        .node_owner
+        .to_account_info()
+        .try_borrow_mut_lamports()? += bond;
+    commit.bond = 0;
+    commit.revealed = true;
+    commit.revealed_at = clock.unix_timestamp;
+    commit.result_hash = result_hash.clone();
+
+    node.total_inferences = node.total_inferences.saturating_add(1);
+    node.last_heartbeat_ts = clock.unix_timestamp;
+    node.active_task_id = 0;
+    hold_stake_for_challenge(node, clock.unix_timestamp)?;
+
+    task.reveal_count = task.reveal_count.saturating_add(1);
+    task.updated_ts = clock.unix_timestamp;
+
+    inference.proposal_id = format!("intent-{}", task_id);
+    inference.task_id = task_id;
+    inference.node = node.owner;
+    inference.workflow = task.workflow;
+    inference.model_capability = node.model_capability;
+    inference.result_hash = result_hash;
+    inference.metadata_hash = metadata_hash;
+    inference.ipfs_cid = ipfs_cid;
+    inference.timestamp = clock.unix_timestamp;
+    inference.confidence_bps = confidence_bps;
+    inference.cache_hit_used = cache_hit_used;
+    inference.proof_hash = [0u8; 32];
+
+    Ok(())
+}
+
+/// The `InferenceResult` of every node that revealed in the current round is
+/// passed as `remaining_accounts`, so no revealed result can be left out. They
+/// are followed by the `ReasoningNode` of every other assigned node, in
+/// `assigned_nodes` order, so those nodes are released from the task.
+#[derive(Accounts)]
+#[instruction(task_id: u64)]
+pub struct TallyReveals<'info> {
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TroTask>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Permissionless once the reveal window has closed, or as soon as every required
+/// node has revealed. The task moves on to verification only when a quorum of
+/// revealed results share a hash; nodes that revealed another hash are recorded
+/// as dissenters. Without a quorum the task reopens for a new round of nodes.
+pub fn tally_reveals<'info>(
+    ctx: Context<'_, '_, 'info, 'info, TallyReveals<'info>>,
+    task_id: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
+    let task = &mut ctx.accounts.task;
+    let clock = Clock::get()?;
+
+    require!(task.commit_reveal, ErrorCode::CommitRevealNotEnabled);
+    require!(
+        task.status == TaskStatus::Reasoning,
+        ErrorCode::TaskNotInReasoning
+    );
+    require!(
+        clock.unix_timestamp > task.reveal_deadline
+            || task.reveal_count == task.required_nodes,
+        ErrorCode::RevealWindowOpen
+    );
+    require!(
+        ctx.remaining_accounts.len() == task.assigned_nodes.len(),
+        ErrorCode::InvalidCommitSet
+    );
+    let (result_infos, idle_infos) = ctx
+        .remaining_accounts
+        .split_at(task.reveal_count as usize);
+
+    let mut revealed: Vec<InferenceResult> = Vec::with_capacity(result_infos.len());
+    for info in result_infos {
+        require_keys_eq!(*info.owner, crate::ID, ErrorCode::InvalidCommitSet);
+        let result = InferenceResult::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+        let (expected, _) = Pubkey::find_program_address(
+            &[
+                INFERENCE_SEED,
+                &task_id.to_le_bytes(),
+                result.node.as_ref(),
+                &[task.round],
+            ],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidCommitSet);
+        require!(
+            task.assigned_nodes.contains(&result.node)
+                && !revealed.iter().any(|other| other.node == result.node),
+            ErrorCode::InvalidCommitSet
+        );
+        revealed.push(result);
+    }
+
+    // Nodes that claimed but never revealed would otherwise stay bound to the task.
+    let idle_nodes = task
+        .assigned_nodes
+        .iter()
+        .filter(|node| !revealed.iter().any(|result| result.node == **node));
+    for (info, idle_node) in idle_infos.iter().zip(idle_nodes) {
+        let (expected, _) = Pubkey::find_program_address(&[NODE_SEED, idle_node.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidCommitSet);
+        let mut node = Account::<ReasoningNode>::try_from(info)?;
+        if node.active_task_id == task_id {
+            node.active_task_id = 0;
+            node.exit(&crate::ID)?;
+        }
+    }
+
+    task.updated_ts = clock.unix_timestamp;
+    task.last_actor = ctx.accounts.caller.key();
+
+    // Most common revealed hash wins; ties go to the hash revealed first.
+    revealed.sort_by_key(|result| result.timestamp);
+    let mut winner: Option<(usize, &InferenceResult)> = None;
+    for result in &revealed {
+        let votes = revealed
+            .iter()
+            .filter(|other| other.result_hash == result.result_hash)
+            .count();
+        if winner.is_none_or(|(best, _)| votes > best) {
+            winner = Some((votes, result));
+        }
+    }
+
+    match winner {
+        Some((votes, result)) if votes >= task.quorum() => {
+            task.reasoning_result = result.result_hash.clone();
+            task.result_node = result.node;
+            task.reasoned_ts = result.timestamp;
+            task.dissenters = revealed
+                .iter()
+                .filter(|other| other.result_hash != result.result_hash)
+                .map(|other| other.node)
+                .collect();
+            task.agreeing_nodes = revealed
+                .iter()
+                .filter(|other| other.result_hash == result.result_hash && other.node != result.node)
+                .map(|other| other.node)
+                .collect();
+            task.status = TaskStatus::Verifying;
+        }
+        _ => start_new_round(task)?,
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(task_id: u64, node_owner: Pubkey, round: u8)]
+pub struct ForfeitUnrevealedCommit<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TASK_SEED, &task_id.to_le_bytes()],
+        bump
+    )]
+    pub task: Account<'info, TroTask>,
+    /// CHECK: receives the commit account rent
+    #[account(mut, address = node_owner)]
+    pub node_owner_account: AccountInfo<'info>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(
+        mut,
+        close = node_owner_account,
+        seeds = [COMMIT_SEED, &task_id.to_le_bytes(), &[round], node_owner.as_ref()],
+        bump
+    )]
+    pub commit: Account<'info, ReasoningCommit>,
+    #[account(
+        mut,
+        seeds = [REWARD_VAULT_SEED],
+        bump,
+        constraint = reward_vault.authority == economy_config.authority
+    )]
+    pub reward_vault: Account<'info, RewardVault>,
     #[account(
-        init,
-        payer = node_owner,
-        space = 8 + InferenceResult::MAX_SIZE,
-        seeds = [INFERENCE_SEED, &task_id.to_le_bytes(), node_owner.key().as_ref()],
+        mut,
+        seeds = [ECONOMY_SEED],
         bump
     )]
-    pub inference_result: Account<'info, InferenceResult>,
-    pub system_program: Program<'info, System>,
+    pub economy_config: Account<'info, EconomyConfig>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
-#[allow(clippy::too_many_arguments)]
-pub fn submit_reasoning(
-    ctx: Context<SubmitReasoning>,
+/// Permissionless once the reveal window has closed: moves the bond of a commit
+/// that was never revealed into the slash pool and frees the node.
+pub fn forfeit_unrevealed_commit(
+    ctx: Context<ForfeitUnrevealedCommit>,
     task_id: u64,
-    result_hash: String,
-    metadata_hash: String,
-    ipfs_cid: String,
-    confidence_bps: u16,
-    cache_hit_used: bool,
+    _node_owner: Pubkey,
+    round: u8,
 ) -> Result<()> {
-    enforce_len(&result_hash, HASH_MAX_LEN)?;
-    enforce_len(&metadata_hash, HASH_MAX_LEN)?;
-    enforce_len(&ipfs_cid, CID_MAX_LEN)?;
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
 
-    let node = &mut ctx.accounts.reasoning_node;
     let task = &mut ctx.accounts.task;
-    let inference = &mut ctx.accounts.inference_result;
+    let commit = &mut ctx.accounts.commit;
     let clock = Clock::get()?;
 
+    let current_round = round == task.round;
     require!(
-        task.status == TaskStatus::Reasoning,
-        ErrorCode::TaskNotInReasoning
+        !current_round
+            || task.status != TaskStatus::Reasoning
+            || clock.unix_timestamp > task.reveal_deadline,
+        ErrorCode::RevealWindowOpen
     );
-    require!(node.active_task_id == task_id, ErrorCode::NodeNotAssigned);
+    require!(!commit.revealed, ErrorCode::AlreadyRevealed);
 
-    node.total_inferences = node.total_inferences.saturating_add(1);
-    if confidence_bps as u32 >= 7_500 {
-        node.successful_inferences = node.successful_inferences.saturating_add(1);
-    }
-    node.last_heartbeat_ts = clock.unix_timestamp;
+    let bond = commit.bond;
+    **commit.to_account_info().try_borrow_mut_lamports()? -= bond;
+    **ctx
+        .accounts
+        .reward_vault
+        .to_account_info()
+        .try_borrow_mut_lamports()? += bond;
+    commit.bond = 0;
 
-    task.reasoning_result = result_hash.clone();
-    task.metadata_hash = metadata_hash.clone();
-    task.ipfs_result = ipfs_cid.clone();
-    task.cache_hit_used = cache_hit_used;
-    task.status = TaskStatus::Verifying;
-    task.last_actor = node.owner;
-    task.updated_ts = clock.unix_timestamp;
+    let reward_vault = &mut ctx.accounts.reward_vault;
+    reward_vault.total_accrued = reward_vault.total_accrued.saturating_add(bond);
+    let economy = &mut ctx.accounts.economy_config;
+    economy.slash_pool = economy.slash_pool.saturating_add(bond);
 
-    inference.proposal_id = format!("intent-{}", task_id);
-    inference.task_id = task_id;
-    inference.node = node.owner;
-    inference.workflow = task.workflow;
-    inference.model_capability = node.model_capability;
-    inference.result_hash = result_hash;
-    inference.metadata_hash = metadata_hash;
-    inference.ipfs_cid = ipfs_cid;
-    inference.timestamp = clock.unix_timestamp;
-    inference.confidence_bps = confidence_bps;
-    inference.cache_hit_used = cache_hit_used;
-    inference.proof_hash = [0u8; 32];
+    if current_round {
+        task.commit_count = task.commit_count.saturating_sub(1);
+    }
+    task.updated_ts = clock.unix_timestamp;
+    let node = &mut ctx.accounts.reasoning_node;
+    if node.active_task_id == task_id {
+        node.active_task_id = 0;
+    }
 
     Ok(())
 }
@@ -305,12 +1086,24 @@ pub fn submit_reasoning(
 pub struct SubmitVerification<'info> {
     #[account(mut)]
     pub verifier: Signer<'info>,
+    #[account(
+        seeds = [COUNCIL_SEED],
+        bump = council.bump,
+        constraint = council.is_member(&verifier.key()) @ ErrorCode::NotCouncilMember
+    )]
+    pub council: Account<'info, Council>,
     #[account(
         mut,
         seeds = [TASK_SEED, &task_id.to_le_bytes()],
         bump
     )]
     pub task: Account<'info, TroTask>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, task.result_node.as_ref()],
+        bump
+    )]
+    pub result_node: Account<'info, ReasoningNode>,
     #[account(
         init_if_needed,
         payer = verifier,
@@ -319,18 +1112,25 @@ pub struct SubmitVerification<'info> {
         bump
     )]
     pub knowledge_graph: Account<'info, KnowledgeGraphState>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn submit_verification(
-    ctx: Context<SubmitVerification>,
-    task_id: u64,
+/// Signed by a council member. A result is verified once; the score feeds the
+/// reputation of the result node and of every node that agreed with it, passed
+/// as `remaining_accounts` in task order. The challenge window starts from this
+/// point, and all of those nodes keep their stake until it closes.
+pub fn submit_verification<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitVerification<'info>>,
+    _task_id: u64,
     verification_score_bps: u16,
     entity_delta: u64,
     relation_delta: u64,
     triplet_root: [u8; 32],
     metadata_uri: String,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
     enforce_len(&metadata_uri, CID_MAX_LEN)?;
     require!(
         verification_score_bps as u32 <= BPS_DENOMINATOR,
@@ -345,6 +1145,7 @@ pub fn submit_verification(
         task.status == TaskStatus::Verifying || task.status == TaskStatus::ProofPending,
         ErrorCode::TaskNotVerifying
     );
+    require!(!task.verified, ErrorCode::TaskAlreadyVerified);
 
     kg.entity_count = kg.entity_count.saturating_add(entity_delta);
     kg.relation_count = kg.relation_count.saturating_add(relation_delta);
@@ -357,6 +1158,7 @@ pub fn submit_verification(
     kg.version = kg.version.saturating_add(1);
 
     task.verification_score_bps = verification_score_bps;
+    task.verified = true;
     task.updated_ts = clock.unix_timestamp;
     task.last_actor = ctx.accounts.verifier.key();
     let base_window = task
@@ -368,6 +1170,19 @@ pub fn submit_verification(
         .unix_timestamp
         .checked_add(adjusted_window)
         .ok_or(ErrorCode::MathOverflow)?;
+    let challenge_period_end = task.challenge_period_end;
+
+    let result_node = &mut ctx.accounts.result_node;
+    result_node.record_verification(verification_score_bps);
+    result_node.extend_challenge_exposure(challenge_period_end);
+    require!(
+        ctx.remaining_accounts.len() == task.agreeing_nodes.len(),
+        ErrorCode::InvalidTaskNodeAccounts
+    );
+    update_task_nodes(ctx.remaining_accounts, &task.agreeing_nodes, |node| {
+        node.record_verification(verification_score_bps);
+        node.extend_challenge_exposure(challenge_period_end);
+    })?;
 
     if task.requires_proof {
         task.status = TaskStatus::ProofPending;
@@ -397,6 +1212,8 @@ pub struct SubmitProof<'info> {
         bump
     )]
     pub proof_registry: Account<'info, ProofRegistry>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -408,6 +1225,8 @@ pub fn submit_proof(
     model_capability: ModelCapability,
     workflow: WorkflowClass,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
     let task = &mut ctx.accounts.task;
     let registry = &mut ctx.accounts.proof_registry;
     let clock = Clock::get()?;
@@ -453,6 +1272,14 @@ pub struct ChallengeTaskResult<'info> {
         bump
     )]
     pub challenge: Account<'info, ChallengeRecord>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, task.result_node.as_ref()],
+        bump
+    )]
+    pub result_node: Account<'info, ReasoningNode>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -463,6 +1290,7 @@ pub fn challenge_result(
     reason: String,
     evidence_ipfs: String,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
     enforce_len(&reason, REASON_MAX_LEN)?;
     enforce_len(&evidence_ipfs, EVIDENCE_MAX_LEN)?;
 
@@ -475,8 +1303,9 @@ pub fn challenge_result(
         task.status == TaskStatus::ReadyForExecution || task.status == TaskStatus::Finalized,
         ErrorCode::TaskNotChallengeable
     );
+    // finalize_task may run at the window end, so a finalized task is never reopened.
     require!(
-        clock.unix_timestamp <= task.challenge_period_end,
+        clock.unix_timestamp < task.challenge_period_end,
         ErrorCode::ChallengeWindowClosed
     );
 
@@ -492,6 +1321,8 @@ pub fn challenge_result(
 
     task.status = TaskStatus::Disputed;
     task.dispute_count = task.dispute_count.saturating_add(1);
+    let node = &mut ctx.accounts.result_node;
+    node.open_challenges = node.open_challenges.saturating_add(1);
     task.last_actor = ctx.accounts.challenger.key();
     task.updated_ts = clock.unix_timestamp;
 
@@ -517,21 +1348,49 @@ pub struct ResolveChallenge<'info> {
         constraint = challenge.challenger == challenger
     )]
     pub challenge: Account<'info, ChallengeRecord>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, task.result_node.as_ref()],
+        bump
+    )]
+    pub result_node: Account<'info, ReasoningNode>,
     #[account(
         mut,
         seeds = [KNOWLEDGE_GRAPH_SEED],
-        bump,
-        constraint = knowledge_graph.authority == dao_authority.key()
+        bump
     )]
     pub knowledge_graph: Account<'info, KnowledgeGraphState>,
+    #[account(seeds = [COUNCIL_SEED], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
-pub fn resolve_challenge(
-    ctx: Context<ResolveChallenge>,
-    _task_id: u64,
-    _challenger: Pubkey,
+/// Nodes that agreed with the challenged result are passed as
+/// `remaining_accounts` in task order when the challenge is upheld or
+/// overturned, since they share the result node's outcome.
+pub fn resolve_challenge<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveChallenge<'info>>,
+    task_id: u64,
+    challenger: Pubkey,
     resolution: ResolutionOutcome,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+    consume_council_action(
+        &ctx.accounts.council,
+        &mut ctx.accounts.council_action,
+        CouncilActionKind::ResolveChallenge {
+            task_id,
+            challenger,
+            resolution,
+        },
+        &ctx.accounts.dao_authority.key(),
+    )?;
+
     let task = &mut ctx.accounts.task;
     let challenge = &mut ctx.accounts.challenge;
     let clock = Clock::get()?;
@@ -549,18 +1408,55 @@ pub fn resolve_challenge(
     challenge.status = ChallengeStatus::Resolved;
     challenge.outcome = resolution;
     challenge.resolved_at = clock.unix_timestamp;
+    let node = &mut ctx.accounts.result_node;
+    node.open_challenges = node.open_challenges.saturating_sub(1);
 
     task.updated_ts = clock.unix_timestamp;
     task.last_actor = ctx.accounts.dao_authority.key();
 
+    if resolution != ResolutionOutcome::Pending {
+        let upheld = resolution == ResolutionOutcome::Upheld;
+        require!(
+            ctx.remaining_accounts.len() == task.agreeing_nodes.len(),
+            ErrorCode::InvalidTaskNodeAccounts
+        );
+        update_task_nodes(ctx.remaining_accounts, &task.agreeing_nodes, |node| {
+            node.record_challenge_outcome(upheld)
+        })?;
+    }
+
     match resolution {
         ResolutionOutcome::Upheld => {
             task.status = TaskStatus::ReadyForExecution;
+            ctx.accounts.result_node.record_challenge_outcome(true);
         }
         ResolutionOutcome::Overturned => {
-            task.status = TaskStatus::Reasoning;
+            let node = &mut ctx.accounts.result_node;
+            node.record_challenge_outcome(false);
             task.reasoning_result = String::new();
             task.verification_score_bps = 0;
+            task.verified = false;
+            task.reasoned_ts = 0;
+            start_new_round(task)?;
+            // A single-node task goes back to the node that produced the result
+            // when it is free to redo it; otherwise any node may claim it.
+            if !task.commit_reveal
+                && node.status == NodeLifecycleStatus::Active
+                && node.active_task_id == 0
+            {
+                node.active_task_id = task_id;
+                node.claimed_ts = clock.unix_timestamp;
+                node.last_heartbeat_ts = clock.unix_timestamp;
+                task.assigned_nodes.push(node.owner);
+                task.claimed_ts = clock.unix_timestamp;
+                task.status = TaskStatus::Reasoning;
+            }
+            let proposal_status = if task.status == TaskStatus::Reasoning {
+                ProposalStatus::Analyzing
+            } else {
+                ProposalStatus::Submitted
+            };
+            sync_linked_proposal(task, ctx.accounts.proposal.as_mut(), proposal_status)?;
         }
         ResolutionOutcome::Pending => {}
     }
@@ -580,9 +1476,29 @@ pub struct FinalizeTask<'info> {
         constraint = task.submitter == authority.key()
     )]
     pub task: Account<'info, TroTask>,
+    /// CHECK: the node's `ReasoningNode`, or empty if the node has since retired
+    #[account(
+        mut,
+        seeds = [NODE_SEED, task.result_node.as_ref()],
+        bump
+    )]
+    pub result_node: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub proposal: Option<Account<'info, Proposal>>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
-pub fn finalize_task(ctx: Context<FinalizeTask>, task_id: u64) -> Result<()> {
+/// Nodes that agreed with the result, then nodes recorded as dissenters, are
+/// passed as `remaining_accounts` in task order. Agreeing nodes are credited
+/// like the result node; dissenters lose reputation now that the quorum result
+/// is final. Nodes that have retired since are skipped.
+pub fn finalize_task<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeTask<'info>>,
+    _task_id: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_TASKS)?;
+
     let task = &mut ctx.accounts.task;
     let clock = Clock::get()?;
 
@@ -598,7 +1514,48 @@ pub fn finalize_task(ctx: Context<FinalizeTask>, task_id: u64) -> Result<()> {
     task.status = TaskStatus::Finalized;
     task.updated_ts = clock.unix_timestamp;
     task.last_actor = ctx.accounts.authority.key();
+    let on_time = task.reasoned_ts.saturating_sub(task.claimed_ts) <= task.workflow.reasoning_budget_secs();
+    let result_info = ctx.accounts.result_node.to_account_info();
+    if !result_info.data_is_empty() {
+        let mut result_node =
+            ReasoningNode::try_deserialize(&mut &result_info.try_borrow_data()?[..])?;
+        result_node.successful_inferences = result_node.successful_inferences.saturating_add(1);
+        result_node.record_finalization(on_time);
+        result_node.try_serialize(&mut &mut result_info.try_borrow_mut_data()?[..])?;
+    }
+
+    require!(
+        ctx.remaining_accounts.len() == task.agreeing_nodes.len() + task.dissenters.len(),
+        ErrorCode::InvalidTaskNodeAccounts
+    );
+    let (agreeing_infos, dissenter_infos) = ctx.remaining_accounts.split_at(task.agreeing_nodes.len());
+    update_task_nodes(agreeing_infos, &task.agreeing_nodes, |node| {
+        node.successful_inferences = node.successful_inferences.saturating_add(1);
+        node.record_finalization(on_time);
+    })?;
+    update_task_nodes(dissenter_infos, &task.dissenters, ReasoningNode::record_dissent)?;
+    sync_linked_proposal(task, ctx.accounts.proposal.as_mut(), ProposalStatus::Completed)?;
+
+    Ok(())
+}
 
+/// Applies `update` to the node account of each owner in `owners`, skipping
+/// nodes that have retired since.
+fn update_task_nodes<'info>(
+    infos: &'info [AccountInfo<'info>],
+    owners: &[Pubkey],
+    mut update: impl FnMut(&mut ReasoningNode),
+) -> Result<()> {
+    for (info, owner) in infos.iter().zip(owners) {
+        let (expected, _) = Pubkey::find_program_address(&[NODE_SEED, owner.as_ref()], &crate::ID);
+        require_keys_eq!(info.key(), expected, ErrorCode::InvalidTaskNodeAccounts);
+        if info.data_is_empty() {
+            continue;
+        }
+        let mut node = Account::<ReasoningNode>::try_from(info)?;
+        update(&mut node);
+        node.exit(&crate::ID)?;
+    }
     Ok(())
 }
 
@@ -625,23 +1582,27 @@ pub struct SlashMaliciousNode<'info> {
         mut,
         seeds = [REWARD_VAULT_SEED],
         bump,
-        constraint = reward_vault.authority == dao_authority.key()
+        constraint = reward_vault.authority == economy_config.authority
     )]
     pub reward_vault: Account<'info, RewardVault>,
     #[account(
         mut,
         seeds = [ECONOMY_SEED],
-        bump,
-        constraint = economy_config.authority == dao_authority.key()
+        bump
     )]
     pub economy_config: Account<'info, EconomyConfig>,
     #[account(
         mut,
         seeds = [KNOWLEDGE_GRAPH_SEED],
-        bump,
-        constraint = knowledge_graph.authority == dao_authority.key()
+        bump
     )]
     pub knowledge_graph: Account<'info, KnowledgeGraphState>,
+    #[account(seeds = [COUNCIL_SEED], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
 pub fn slash_malicious_node(
@@ -650,7 +1611,18 @@ pub fn slash_malicious_node(
     slash_amount: u64,
     suspend: bool,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
     require!(slash_amount > 0, ErrorCode::InvalidSlashAmount);
+    consume_council_action(
+        &ctx.accounts.council,
+        &mut ctx.accounts.council_action,
+        CouncilActionKind::SlashNode {
+            node_owner,
+            amount: slash_amount,
+            suspend,
+        },
+        &ctx.accounts.dao_authority.key(),
+    )?;
     require!(ctx.accounts.punished_node.owner == node_owner, ErrorCode::UnauthorizedActor);
     require!(
         ctx.accounts.stake_vault.total_stake >= slash_amount,
@@ -666,8 +1638,7 @@ pub fn slash_malicious_node(
     let reward_vault = &mut ctx.accounts.reward_vault;
     let economy = &mut ctx.accounts.economy_config;
 
-    node.pending_slash_amount = node.pending_slash_amount.saturating_add(slash_amount);
-    node.stake_amount = node.stake_amount.saturating_sub(slash_amount);
+    node.apply_slash(slash_amount);
     stake_vault.total_stake = stake_vault.total_stake.saturating_sub(slash_amount);
 
     **stake_vault.to_account_info().try_borrow_mut_lamports()? -= slash_amount;
@@ -716,14 +1687,13 @@ pub fn initialize_economy(
     stake_floor: u64,
     stake_ceiling: u64,
     cycle_length_slots: u64,
+    task_timeout_secs: i64,
+    offline_grace_secs: i64,
+    offline_penalty_bps_per_hour: u16,
 ) -> Result<()> {
-    require!(stake_ceiling >= stake_floor, ErrorCode::InvalidStakeRange);
-    require!(
-        base_reward_rate_bps as u32 <= BPS_DENOMINATOR
-            && high_perf_multiplier_bps as u32 <= BPS_DENOMINATOR
-            && low_perf_penalty_bps as u32 <= BPS_DENOMINATOR,
-        ErrorCode::InvalidScore
-    );
+    validate_stake_range(stake_floor, stake_ceiling)?;
+    validate_reward_rates(base_reward_rate_bps, high_perf_multiplier_bps, low_perf_penalty_bps)?;
+    validate_liveness_params(task_timeout_secs, offline_grace_secs, offline_penalty_bps_per_hour)?;
 
     let economy = &mut ctx.accounts.economy_config;
     let reward_vault = &mut ctx.accounts.reward_vault;
@@ -740,12 +1710,15 @@ pub fn initialize_economy(
     economy.cycle_length_slots = cycle_length_slots;
     economy.slash_pool = 0;
     economy.last_rebalance_slot = clock.slot;
-    economy.bump = *ctx.bumps.get("economy_config").unwrap_or(&0);
+    economy.task_timeout_secs = task_timeout_secs;
+    economy.offline_grace_secs = offline_grace_secs;
+    economy.offline_penalty_bps_per_hour = offline_penalty_bps_per_hour;
+    economy.bump = ctx.bumps.economy_config;
 
     reward_vault.authority = authority;
     reward_vault.total_accrued = 0;
     reward_vault.total_distributed = 0;
-    reward_vault.bump = *ctx.bumps.get("reward_vault").unwrap_or(&0);
+    reward_vault.bump = ctx.bumps.reward_vault;
 
     Ok(())
 }
@@ -768,15 +1741,22 @@ pub struct DepositStake<'info> {
         bump
     )]
     pub stake_vault: Account<'info, StakeVault>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn deposit_stake(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
     require!(amount > 0, ErrorCode::InvalidAmount);
 
-    let stake_vault_bump = *ctx.bumps.get("stake_vault").unwrap();
+    let stake_vault_bump = ctx.bumps.stake_vault;
     let node = &mut ctx.accounts.reasoning_node;
     let stake_vault = &mut ctx.accounts.stake_vault;
+    require!(
+        node.status == NodeLifecycleStatus::Active,
+        ErrorCode::NodeInactive
+    );
 
     system_program::transfer(
         CpiContext::new(
@@ -805,7 +1785,66 @@ pub fn deposit_stake(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
 }
 
 #[derive(Accounts)]
-pub struct WithdrawStake<'info> {
+pub struct RequestUnbond<'info> {
+    pub node_owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [NODE_SEED, node_owner.key().as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Moves stake into unbonding. It stays in the stake vault, and slashable, until
+/// the cooldown has passed and every challenge window on the node's results has
+/// closed. Unbonding all stake retires the node. Suspended nodes may unbond
+/// too, so a suspension does not lock their stake for good; a retired node
+/// cannot rejoin, so retiring never clears a penalty.
+pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let node = &mut ctx.accounts.reasoning_node;
+    let clock = Clock::get()?;
+
+    require!(
+        node.status == NodeLifecycleStatus::Active
+            || node.status == NodeLifecycleStatus::Suspended,
+        ErrorCode::NodeInactive
+    );
+
+    require!(node.stake_amount >= amount, ErrorCode::InsufficientStake);
+    let remaining = node.stake_amount.saturating_sub(amount);
+    require!(
+        remaining == 0 || remaining >= node.dynamic_min_stake,
+        ErrorCode::StakeBelowMinimum
+    );
+    if remaining == 0 {
+        require!(node.active_task_id == 0, ErrorCode::NodeBusy);
+        node.status = NodeLifecycleStatus::Retired;
+    }
+
+    let cooldown_end = clock
+        .unix_timestamp
+        .checked_add(MIN_CHALLENGE_WINDOW)
+        .ok_or(ErrorCode::MathOverflow)?;
+    node.stake_amount = remaining;
+    node.unbonding_amount = node
+        .unbonding_amount
+        .checked_add(amount)
+        .ok_or(ErrorCode::MathOverflow)?;
+    node.unbond_available_ts = node
+        .unbond_available_ts
+        .max(cooldown_end)
+        .max(node.challenge_exposure_until);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CompleteUnbond<'info> {
     #[account(mut)]
     pub node_owner: Signer<'info>,
     #[account(
@@ -820,32 +1859,91 @@ pub struct WithdrawStake<'info> {
         bump = reasoning_node.stake_vault_bump,
         constraint = stake_vault.owner == node_owner.key()
     )]
-    pub stake_vault: Account<'info, StakeVault>,
+    pub stake_vault: Option<Account<'info, StakeVault>>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
-pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount: u64) -> Result<()> {
-    require!(amount > 0, ErrorCode::InvalidAmount);
+/// Pays out whatever is left of the unbonding stake after any slashing. Only
+/// escrowed stake is paid; stake credited before registrations were escrowed has
+/// nothing behind it and is written off, so the node can still retire.
+pub fn complete_unbond(ctx: Context<CompleteUnbond>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
 
     let node = &mut ctx.accounts.reasoning_node;
-    let stake_vault = &mut ctx.accounts.stake_vault;
+    let clock = Clock::get()?;
 
-    require!(node.stake_amount >= amount, ErrorCode::InsufficientStake);
-    require!(
-        stake_vault.to_account_info().lamports() >= amount,
-        ErrorCode::InsufficientStake
-    );
-    let remaining = node.stake_amount.saturating_sub(amount);
-    require!(remaining >= node.dynamic_min_stake, ErrorCode::StakeBelowMinimum);
+    let amount = node.unbonding_amount;
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(node.stake_unlocked(clock.unix_timestamp), ErrorCode::StakeLocked);
 
-    node.stake_amount = remaining;
-    stake_vault.total_stake = stake_vault.total_stake.saturating_sub(amount);
+    node.unbonding_amount = 0;
+    let Some(stake_vault) = ctx.accounts.stake_vault.as_mut() else {
+        require!(node.stake_vault_bump == 0, ErrorCode::MissingStakeVault);
+        return Ok(());
+    };
+    let payout = amount.min(withdrawable_stake(stake_vault)?);
+    if payout == 0 {
+        return Ok(());
+    }
+    stake_vault.total_stake = stake_vault.total_stake.saturating_sub(payout);
 
-    **stake_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **stake_vault.to_account_info().try_borrow_mut_lamports()? -= payout;
     **ctx
         .accounts
         .node_owner
         .to_account_info()
-        .try_borrow_mut_lamports()? += amount;
+        .try_borrow_mut_lamports()? += payout;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RetireNode<'info> {
+    #[account(mut)]
+    pub node_owner: Signer<'info>,
+    #[account(
+        mut,
+        close = node_owner,
+        seeds = [NODE_SEED, node_owner.key().as_ref()],
+        bump
+    )]
+    pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(
+        mut,
+        close = node_owner,
+        seeds = [STAKE_VAULT_SEED, node_owner.key().as_ref()],
+        bump = reasoning_node.stake_vault_bump,
+        constraint = stake_vault.owner == node_owner.key()
+    )]
+    pub stake_vault: Option<Account<'info, StakeVault>>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
+}
+
+/// Closes a retired node, and its stake vault, once all stake has been unbonded
+/// and rewards settled.
+pub fn retire_node(ctx: Context<RetireNode>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
+
+    let node = &ctx.accounts.reasoning_node;
+    let clock = Clock::get()?;
+
+    require!(
+        node.status == NodeLifecycleStatus::Retired,
+        ErrorCode::NodeNotRetired
+    );
+    require!(
+        node.stake_amount == 0 && node.unbonding_amount == 0,
+        ErrorCode::StakeStillBonded
+    );
+    require!(node.active_task_id == 0, ErrorCode::NodeBusy);
+    require!(node.stake_unlocked(clock.unix_timestamp), ErrorCode::StakeLocked);
+    require!(node.pending_rewards == 0, ErrorCode::UnsettledRewards);
+    match ctx.accounts.stake_vault.as_ref() {
+        Some(stake_vault) => require!(stake_vault.total_stake == 0, ErrorCode::StakeStillBonded),
+        None => require!(node.stake_vault_bump == 0, ErrorCode::MissingStakeVault),
+    }
 
     Ok(())
 }
@@ -857,10 +1955,13 @@ pub struct UpdateDynamicStake<'info> {
     #[account(
         mut,
         seeds = [ECONOMY_SEED],
-        bump,
-        constraint = economy_config.authority == dao_authority.key()
+        bump
     )]
     pub economy_config: Account<'info, EconomyConfig>,
+    #[account(seeds = [COUNCIL_SEED], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
     /// CHECK: only used for PDA derivation
     pub node_owner: UncheckedAccount<'info>,
     #[account(
@@ -870,9 +1971,21 @@ pub struct UpdateDynamicStake<'info> {
         constraint = reasoning_node.owner == node_owner.key()
     )]
     pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
 pub fn update_dynamic_stake(ctx: Context<UpdateDynamicStake>) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_STAKING)?;
+    consume_council_action(
+        &ctx.accounts.council,
+        &mut ctx.accounts.council_action,
+        CouncilActionKind::UpdateDynamicStake {
+            node_owner: ctx.accounts.node_owner.key(),
+        },
+        &ctx.accounts.dao_authority.key(),
+    )?;
+
     let economy = &mut ctx.accounts.economy_config;
     let node = &mut ctx.accounts.reasoning_node;
     let clock = Clock::get()?;
@@ -931,6 +2044,8 @@ pub struct QueueRewardSettlement<'info> {
         constraint = reward_vault.authority == economy_config.authority
     )]
     pub reward_vault: Account<'info, RewardVault>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
     pub system_program: Program<'info, System>,
 }
 
@@ -939,6 +2054,7 @@ pub fn queue_reward_settlement(
     _task_id: u64,
     amount: u64,
 ) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_REWARDS)?;
     require!(amount > 0, ErrorCode::InvalidAmount);
 
     let task = &mut ctx.accounts.task;
@@ -947,17 +2063,38 @@ pub fn queue_reward_settlement(
         ErrorCode::TaskNotExecutable
     );
     require!(task.stake_pool >= amount, ErrorCode::InsufficientStake);
+    let node_owner = ctx.accounts.node_owner.key();
+    require!(
+        node_owner == task.result_node || task.agreeing_nodes.contains(&node_owner),
+        ErrorCode::NodeNotAssigned
+    );
 
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.payer.to_account_info(),
-                to: ctx.accounts.reward_vault.to_account_info(),
-            },
-        ),
-        amount,
-    )?;
+    if task.stake_escrowed {
+        // Rewards prefunded into the task account move straight to the vault,
+        // so only the submitter who escrowed them may direct them.
+        require_keys_eq!(
+            ctx.accounts.payer.key(),
+            task.submitter,
+            ErrorCode::UnauthorizedActor
+        );
+        **task.to_account_info().try_borrow_mut_lamports()? -= amount;
+        **ctx
+            .accounts
+            .reward_vault
+            .to_account_info()
+            .try_borrow_mut_lamports()? += amount;
+    } else {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
 
     let node = &mut ctx.accounts.reasoning_node;
     let reward_vault = &mut ctx.accounts.reward_vault;
@@ -985,10 +2122,13 @@ pub struct SettleReward<'info> {
     #[account(
         mut,
         seeds = [ECONOMY_SEED],
-        bump,
-        constraint = economy_config.authority == dao_authority.key()
+        bump
     )]
     pub economy_config: Account<'info, EconomyConfig>,
+    #[account(seeds = [COUNCIL_SEED], bump = council.bump)]
+    pub council: Account<'info, Council>,
+    #[account(mut)]
+    pub council_action: Account<'info, CouncilAction>,
     #[account(
         mut,
         seeds = [REWARD_VAULT_SEED],
@@ -1006,9 +2146,24 @@ pub struct SettleReward<'info> {
         constraint = reasoning_node.owner == node_owner.key()
     )]
     pub reasoning_node: Account<'info, ReasoningNode>,
+    #[account(seeds = [PROGRAM_STATE_SEED], bump = program_state.bump)]
+    pub program_state: Account<'info, ProgramState>,
 }
 
-pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
+/// Pays out at most `max_amount` of the node's pending rewards, as approved by
+/// the council action.
+pub fn settle_reward(ctx: Context<SettleReward>, max_amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.program_state, PAUSE_REWARDS)?;
+    consume_council_action(
+        &ctx.accounts.council,
+        &mut ctx.accounts.council_action,
+        CouncilActionKind::SettleReward {
+            node_owner: ctx.accounts.node_owner.key(),
+            max_amount,
+        },
+        &ctx.accounts.dao_authority.key(),
+    )?;
+
     let node = &mut ctx.accounts.reasoning_node;
     let reward_vault = &mut ctx.accounts.reward_vault;
 
@@ -1016,7 +2171,7 @@ pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
     require!(pending > 0, ErrorCode::InsufficientPendingRewards);
 
     let available = reward_vault.to_account_info().lamports();
-    let payout = pending.min(available);
+    let payout = pending.min(max_amount).min(available);
     require!(payout > 0, ErrorCode::InsufficientPendingRewards);
 
     node.pending_rewards = node.pending_rewards.saturating_sub(payout);
@@ -1033,6 +2188,48 @@ pub fn settle_reward(ctx: Context<SettleReward>) -> Result<()> {
     Ok(())
 }
 
+/// Escrowed stake the vault can pay out while staying rent exempt.
+fn withdrawable_stake(stake_vault: &Account<StakeVault>) -> Result<u64> {
+    let info = stake_vault.to_account_info();
+    let reserve = Rent::get()?.minimum_balance(info.data_len());
+    Ok(stake_vault
+        .total_stake
+        .min(info.lamports().saturating_sub(reserve)))
+}
+
+pub(crate) fn validate_stake_range(stake_floor: u64, stake_ceiling: u64) -> Result<()> {
+    require!(stake_ceiling >= stake_floor, ErrorCode::InvalidStakeRange);
+    Ok(())
+}
+
+pub(crate) fn validate_reward_rates(
+    base_reward_rate_bps: u16,
+    high_perf_multiplier_bps: u16,
+    low_perf_penalty_bps: u16,
+) -> Result<()> {
+    require!(
+        base_reward_rate_bps as u32 <= BPS_DENOMINATOR
+            && high_perf_multiplier_bps as u32 <= BPS_DENOMINATOR
+            && low_perf_penalty_bps as u32 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidScore
+    );
+    Ok(())
+}
+
+pub(crate) fn validate_liveness_params(
+    task_timeout_secs: i64,
+    offline_grace_secs: i64,
+    offline_penalty_bps_per_hour: u16,
+) -> Result<()> {
+    require!(
+        task_timeout_secs > 0
+            && offline_grace_secs >= 0
+            && offline_penalty_bps_per_hour as u32 <= BPS_DENOMINATOR,
+        ErrorCode::InvalidLivenessParams
+    );
+    Ok(())
+}
+
 fn enforce_len(value: &str, limit: usize) -> Result<()> {
     require!(value.len() <= limit, ErrorCode::StringTooLong);
     Ok(())
@@ -3,17 +3,18 @@ use anchor_lang::prelude::*;
 pub mod instructions;
 pub mod state;
 
-use instructions::{
-    AggregateResults, ChallengeTaskResult, ClaimReward, ClaimTask, CreateGovernanceProposal,
-    CreateTrainingTask, DistributeInferenceReward, DistributeRewards, ExecuteProposal,
-    FinalizeTask, RateNode, RegisterNode, RegisterReasoningNode, ResolveChallenge, RewardType,
-    SlashMaliciousNode, SubmitGradient, SubmitInference, SubmitIntentTask, SubmitProof,
-    SubmitProposal, SubmitReasoning, SubmitVerification, VoteOnProposal,
-};
+// `#[program]` resolves each instruction's generated account helpers at the crate
+// root; the handler functions share their names with the program's entrypoints.
+#[allow(ambiguous_glob_reexports)]
+pub use instructions::*;
 
 use state::{
-    governance::{GovernanceProposalType, GovernanceVoteType, ModelConfig},
-    ModelCapability, ProofPolicy, ResolutionOutcome, TaskCriticality, TaskType, WorkflowClass,
+    governance::{
+        GovernanceProposalType, GovernanceVoteType, ModelConfig, NodeStakeUpdate, PauseUpdate,
+        ProgramUpgrade, ProposalInstruction, RewardRateUpdate, TreasurySpend, VoteStrategy,
+    },
+    CouncilActionKind, ModelCapability, ProofPolicy, ProposalAnalysis, ResolutionOutcome,
+    TaskCriticality, TaskType, WorkflowClass,
 };
 
 declare_id!("GhqfJkCcxJSqz58yWGGxJLis6MB3987SFkz4V1fdQSX2");
@@ -27,40 +28,250 @@ pub mod daollm {
         ctx: Context<SubmitProposal>,
         proposal_id: String,
         ipfs_hash: String,
+        analysis: Option<ProposalAnalysis>,
     ) -> Result<()> {
-        instructions::data_contribution::submit_proposal(ctx, proposal_id, ipfs_hash)
+        instructions::data_contribution::submit_proposal(ctx, proposal_id, ipfs_hash, analysis)
     }
 
-    // Inference Network Instructions
-    pub fn register_node(ctx: Context<RegisterNode>, stake_amount: u64) -> Result<()> {
-        instructions::inference_network::register_node(ctx, stake_amount)
+    pub fn amend_proposal(
+        ctx: Context<AmendProposal>,
+        proposal_id: String,
+        ipfs_hash: String,
+    ) -> Result<()> {
+        instructions::data_contribution::amend_proposal(ctx, proposal_id, ipfs_hash)
+    }
+
+    pub fn cancel_proposal_analysis(
+        ctx: Context<CancelProposalAnalysis>,
+        proposal_id: String,
+    ) -> Result<()> {
+        instructions::data_contribution::cancel_proposal_analysis(ctx, proposal_id)
+    }
+
+    // Node Instructions
+    pub fn register_reasoning_node(
+        ctx: Context<RegisterReasoningNode>,
+        controller: Pubkey,
+        model_capability: ModelCapability,
+        workflow_affinity: WorkflowClass,
+        base_stake_requirement: u64,
+        initial_stake: u64,
+    ) -> Result<()> {
+        instructions::tro::register_reasoning_node(
+            ctx,
+            controller,
+            model_capability,
+            workflow_affinity,
+            base_stake_requirement,
+            initial_stake,
+        )
     }
 
+    pub fn migrate_legacy_node(
+        ctx: Context<MigrateLegacyNode>,
+        model_capability: ModelCapability,
+        workflow_affinity: WorkflowClass,
+    ) -> Result<()> {
+        instructions::inference_network::migrate_legacy_node(
+            ctx,
+            model_capability,
+            workflow_affinity,
+        )
+    }
+
+    pub fn deposit_stake(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
+        instructions::tro::deposit_stake(ctx, amount)
+    }
+
+    pub fn request_unbond(ctx: Context<RequestUnbond>, amount: u64) -> Result<()> {
+        instructions::tro::request_unbond(ctx, amount)
+    }
+
+    pub fn complete_unbond(ctx: Context<CompleteUnbond>) -> Result<()> {
+        instructions::tro::complete_unbond(ctx)
+    }
+
+    pub fn retire_node(ctx: Context<RetireNode>) -> Result<()> {
+        instructions::tro::retire_node(ctx)
+    }
+
+    pub fn update_dynamic_stake(ctx: Context<UpdateDynamicStake>) -> Result<()> {
+        instructions::tro::update_dynamic_stake(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn initialize_economy(
+        ctx: Context<InitializeEconomy>,
+        base_reward_rate_bps: u16,
+        high_perf_multiplier_bps: u16,
+        low_perf_penalty_bps: u16,
+        stake_floor: u64,
+        stake_ceiling: u64,
+        cycle_length_slots: u64,
+        task_timeout_secs: i64,
+        offline_grace_secs: i64,
+        offline_penalty_bps_per_hour: u16,
+    ) -> Result<()> {
+        instructions::tro::initialize_economy(
+            ctx,
+            base_reward_rate_bps,
+            high_perf_multiplier_bps,
+            low_perf_penalty_bps,
+            stake_floor,
+            stake_ceiling,
+            cycle_length_slots,
+            task_timeout_secs,
+            offline_grace_secs,
+            offline_penalty_bps_per_hour,
+        )
+    }
+
+    pub fn queue_reward_settlement(
+        ctx: Context<QueueRewardSettlement>,
+        task_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::tro::queue_reward_settlement(ctx, task_id, amount)
+    }
+
+    pub fn settle_reward(ctx: Context<SettleReward>, max_amount: u64) -> Result<()> {
+        instructions::tro::settle_reward(ctx, max_amount)
+    }
+
+    // Inference Network Instructions
     pub fn submit_inference(
         ctx: Context<SubmitInference>,
         proposal_id: String,
         result_hash: String,
-        confidence: u8,
+        confidence_bps: u16,
     ) -> Result<()> {
-        instructions::inference_network::submit_inference(ctx, proposal_id, result_hash, confidence)
+        instructions::inference_network::submit_inference(
+            ctx,
+            proposal_id,
+            result_hash,
+            confidence_bps,
+        )
     }
 
-    pub fn aggregate_results(ctx: Context<AggregateResults>, proposal_id: String) -> Result<()> {
-        instructions::inference_network::aggregate_results(ctx, proposal_id)
+    pub fn aggregate_results(
+        ctx: Context<AggregateResults>,
+        proposal_id: String,
+        consensus_hash: String,
+        contributors_root: [u8; 32],
+        agreement_bps: u16,
+    ) -> Result<()> {
+        instructions::inference_network::aggregate_results(
+            ctx,
+            proposal_id,
+            consensus_hash,
+            contributors_root,
+            agreement_bps,
+        )
     }
 
-    pub fn rate_node(ctx: Context<RateNode>, node_address: Pubkey, score: u8) -> Result<()> {
-        instructions::inference_network::rate_node(ctx, node_address, score)
+    pub fn rate_node(
+        ctx: Context<RateNode>,
+        node_address: Pubkey,
+        task_id: u64,
+        score: u8,
+    ) -> Result<()> {
+        instructions::inference_network::rate_node(ctx, node_address, task_id, score)
     }
 
     // Governance Instructions
+    pub fn initialize_governance(ctx: Context<InitializeGovernance>) -> Result<()> {
+        instructions::governance::initialize_governance(ctx)
+    }
+
+    pub fn set_proposal_threshold(
+        ctx: Context<SetProposalThreshold>,
+        proposal_type: GovernanceProposalType,
+        quorum_bps: u16,
+        approval_threshold_bps: u16,
+    ) -> Result<()> {
+        instructions::governance::set_proposal_threshold(
+            ctx,
+            proposal_type,
+            quorum_bps,
+            approval_threshold_bps,
+        )
+    }
+
+    pub fn set_proposal_timelock(
+        ctx: Context<SetProposalTimelock>,
+        proposal_type: GovernanceProposalType,
+        delay_secs: i64,
+    ) -> Result<()> {
+        instructions::governance::set_proposal_timelock(ctx, proposal_type, delay_secs)
+    }
+
+    pub fn set_proposal_deposit(
+        ctx: Context<SetProposalDeposit>,
+        proposal_deposit: u64,
+        min_proposer_balance: u64,
+    ) -> Result<()> {
+        instructions::governance::set_proposal_deposit(ctx, proposal_deposit, min_proposer_balance)
+    }
+
+    pub fn set_guardians(
+        ctx: Context<SetGuardians>,
+        guardians: Vec<Pubkey>,
+        guardian_threshold: u8,
+    ) -> Result<()> {
+        instructions::governance::set_guardians(ctx, guardians, guardian_threshold)
+    }
+
+    pub fn initialize_program_state(ctx: Context<InitializeProgramState>) -> Result<()> {
+        instructions::governance::initialize_program_state(ctx)
+    }
+
+    pub fn initialize_model_config(
+        ctx: Context<InitializeModelConfig>,
+        initial_config: ModelConfig,
+    ) -> Result<()> {
+        instructions::governance::initialize_model_config(ctx, initial_config)
+    }
+
+    pub fn guardian_pause(
+        ctx: Context<GuardianPause>,
+        pause_all: bool,
+        paused_groups: u8,
+    ) -> Result<()> {
+        instructions::governance::guardian_pause(ctx, pause_all, paused_groups)
+    }
+
+    pub fn create_voter_record(ctx: Context<CreateVoterRecord>) -> Result<()> {
+        instructions::governance::create_voter_record(ctx)
+    }
+
+    pub fn deposit_governance_tokens(
+        ctx: Context<DepositGovernanceTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::governance::deposit_governance_tokens(ctx, amount)
+    }
+
+    pub fn withdraw_governance_tokens(
+        ctx: Context<WithdrawGovernanceTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::governance::withdraw_governance_tokens(ctx, amount)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn create_governance_proposal(
         ctx: Context<CreateGovernanceProposal>,
         proposal_id: u64,
         proposal_type: GovernanceProposalType,
         description: String,
         target_config: Option<ModelConfig>,
+        reward_rate_update: Option<RewardRateUpdate>,
+        node_stake_update: Option<NodeStakeUpdate>,
+        pause_update: Option<PauseUpdate>,
+        program_upgrade: Option<ProgramUpgrade>,
+        treasury_spend: Option<TreasurySpend>,
         voting_duration: i64,
+        vote_strategy: VoteStrategy,
     ) -> Result<()> {
         instructions::governance::create_governance_proposal(
             ctx,
@@ -68,7 +279,13 @@ pub mod daollm {
             proposal_type,
             description,
             target_config,
+            reward_rate_update,
+            node_stake_update,
+            pause_update,
+            program_upgrade,
+            treasury_spend,
             voting_duration,
+            vote_strategy,
         )
     }
 
@@ -76,12 +293,78 @@ pub mod daollm {
         ctx: Context<VoteOnProposal>,
         proposal_id: u64,
         vote_type: GovernanceVoteType,
-        voting_power: u64,
+        conviction_periods: u8,
     ) -> Result<()> {
-        instructions::governance::vote_on_proposal(ctx, proposal_id, vote_type, voting_power)
+        instructions::governance::vote_on_proposal(ctx, proposal_id, vote_type, conviction_periods)
     }
 
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>, proposal_id: u64) -> Result<()> {
+    pub fn change_vote(
+        ctx: Context<ChangeVote>,
+        proposal_id: u64,
+        vote_type: GovernanceVoteType,
+    ) -> Result<()> {
+        instructions::governance::change_vote(ctx, proposal_id, vote_type)
+    }
+
+    pub fn withdraw_vote(ctx: Context<WithdrawVote>, proposal_id: u64) -> Result<()> {
+        instructions::governance::withdraw_vote(ctx, proposal_id)
+    }
+
+    pub fn submit_signed_votes(ctx: Context<SubmitSignedVotes>, proposal_id: u64) -> Result<()> {
+        instructions::governance::submit_signed_votes(ctx, proposal_id)
+    }
+
+    pub fn delegate_votes(
+        ctx: Context<DelegateVotes>,
+        delegate: Pubkey,
+        scope: Option<GovernanceProposalType>,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::governance::delegate_votes(ctx, delegate, scope, expires_at)
+    }
+
+    pub fn revoke_delegation(ctx: Context<RevokeDelegation>) -> Result<()> {
+        instructions::governance::revoke_delegation(ctx)
+    }
+
+    pub fn close_delegation(ctx: Context<CloseDelegation>) -> Result<()> {
+        instructions::governance::close_delegation(ctx)
+    }
+
+    pub fn attach_proposal_instructions(
+        ctx: Context<AttachProposalInstructions>,
+        proposal_id: u64,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        instructions::governance::attach_proposal_instructions(ctx, proposal_id, instructions)
+    }
+
+    pub fn cancel_governance_proposal(
+        ctx: Context<CancelGovernanceProposal>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        instructions::governance::cancel_governance_proposal(ctx, proposal_id)
+    }
+
+    pub fn finalize_governance_proposal(
+        ctx: Context<FinalizeGovernanceProposal>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        instructions::governance::finalize_governance_proposal(ctx, proposal_id)
+    }
+
+    pub fn queue_proposal(ctx: Context<QueueProposal>, proposal_id: u64) -> Result<()> {
+        instructions::governance::queue_proposal(ctx, proposal_id)
+    }
+
+    pub fn veto_proposal(ctx: Context<VetoProposal>, proposal_id: u64) -> Result<()> {
+        instructions::governance::veto_proposal(ctx, proposal_id)
+    }
+
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+        proposal_id: u64,
+    ) -> Result<()> {
         instructions::governance::execute_proposal(ctx, proposal_id)
     }
 
@@ -127,6 +410,7 @@ pub mod daollm {
     }
 
     // TRO Instructions
+    #[allow(clippy::too_many_arguments)]
     pub fn submit_intent_task(
         ctx: Context<SubmitIntentTask>,
         task_id: u64,
@@ -155,28 +439,22 @@ pub mod daollm {
         )
     }
 
-    pub fn register_reasoning_node(
-        ctx: Context<RegisterReasoningNode>,
-        controller: Pubkey,
-        model_capability: ModelCapability,
-        workflow_affinity: WorkflowClass,
-        base_stake_requirement: u64,
-        initial_stake: u64,
-    ) -> Result<()> {
-        instructions::tro::register_reasoning_node(
-            ctx,
-            controller,
-            model_capability,
-            workflow_affinity,
-            base_stake_requirement,
-            initial_stake,
-        )
-    }
-
     pub fn claim_task(ctx: Context<ClaimTask>, task_id: u64) -> Result<()> {
         instructions::tro::claim_task(ctx, task_id)
     }
 
+    pub fn heartbeat(ctx: Context<Heartbeat>, node_owner: Pubkey) -> Result<()> {
+        instructions::tro::heartbeat(ctx, node_owner)
+    }
+
+    pub fn reclaim_stalled_task(
+        ctx: Context<ReclaimStalledTask>,
+        task_id: u64,
+        node_owner: Pubkey,
+    ) -> Result<()> {
+        instructions::tro::reclaim_stalled_task(ctx, task_id, node_owner)
+    }
+
     pub fn submit_reasoning(
         ctx: Context<SubmitReasoning>,
         task_id: u64,
@@ -197,8 +475,55 @@ pub mod daollm {
         )
     }
 
-    pub fn submit_verification(
-        ctx: Context<SubmitVerification>,
+    pub fn commit_reasoning(
+        ctx: Context<CommitReasoning>,
+        task_id: u64,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::tro::commit_reasoning(ctx, task_id, commitment)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn reveal_reasoning(
+        ctx: Context<RevealReasoning>,
+        task_id: u64,
+        result_hash: String,
+        salt: [u8; 32],
+        metadata_hash: String,
+        ipfs_cid: String,
+        confidence_bps: u16,
+        cache_hit_used: bool,
+    ) -> Result<()> {
+        instructions::tro::reveal_reasoning(
+            ctx,
+            task_id,
+            result_hash,
+            salt,
+            metadata_hash,
+            ipfs_cid,
+            confidence_bps,
+            cache_hit_used,
+        )
+    }
+
+    pub fn tally_reveals<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TallyReveals<'info>>,
+        task_id: u64,
+    ) -> Result<()> {
+        instructions::tro::tally_reveals(ctx, task_id)
+    }
+
+    pub fn forfeit_unrevealed_commit(
+        ctx: Context<ForfeitUnrevealedCommit>,
+        task_id: u64,
+        node_owner: Pubkey,
+        round: u8,
+    ) -> Result<()> {
+        instructions::tro::forfeit_unrevealed_commit(ctx, task_id, node_owner, round)
+    }
+
+    pub fn submit_verification<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitVerification<'info>>,
         task_id: u64,
         verification_score_bps: u16,
         entity_delta: u64,
@@ -245,8 +570,8 @@ pub mod daollm {
         instructions::tro::challenge_result(ctx, task_id, stake, reason, evidence_ipfs)
     }
 
-    pub fn resolve_challenge(
-        ctx: Context<ResolveChallenge>,
+    pub fn resolve_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveChallenge<'info>>,
         task_id: u64,
         challenger: Pubkey,
         resolution: ResolutionOutcome,
@@ -254,7 +579,10 @@ pub mod daollm {
         instructions::tro::resolve_challenge(ctx, task_id, challenger, resolution)
     }
 
-    pub fn finalize_task(ctx: Context<FinalizeTask>, task_id: u64) -> Result<()> {
+    pub fn finalize_task<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeTask<'info>>,
+        task_id: u64,
+    ) -> Result<()> {
         instructions::tro::finalize_task(ctx, task_id)
     }
 
@@ -266,6 +594,53 @@ pub mod daollm {
     ) -> Result<()> {
         instructions::tro::slash_malicious_node(ctx, node_owner, slash_amount, suspend)
     }
+
+    // Council Instructions
+    pub fn initialize_council(
+        ctx: Context<InitializeCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::council::initialize_council(ctx, members, threshold)
+    }
+
+    pub fn set_council_members(
+        ctx: Context<SetCouncilMembers>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::council::set_council_members(ctx, members, threshold)
+    }
+
+    pub fn propose_council_action(
+        ctx: Context<ProposeCouncilAction>,
+        action_id: u64,
+        action: CouncilActionKind,
+    ) -> Result<()> {
+        instructions::council::propose_council_action(ctx, action_id, action)
+    }
+
+    pub fn approve_council_action(ctx: Context<ApproveCouncilAction>, action_id: u64) -> Result<()> {
+        instructions::council::approve_council_action(ctx, action_id)
+    }
+
+    // Treasury Instructions
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        instructions::treasury::initialize_treasury(ctx)
+    }
+
+    pub fn set_treasury_spend_limit(
+        ctx: Context<SetTreasurySpendLimit>,
+        mint: Option<Pubkey>,
+        max_per_period: u64,
+        period_secs: i64,
+    ) -> Result<()> {
+        instructions::treasury::set_treasury_spend_limit(ctx, mint, max_per_period, period_secs)
+    }
+
+    pub fn sweep_slash_pool(ctx: Context<SweepSlashPool>) -> Result<()> {
+        instructions::treasury::sweep_slash_pool(ctx)
+    }
 }
 
 #[error_code]
@@ -320,4 +695,162 @@ pub enum ErrorCode {
     TaskNotExecutable,
     #[msg("Invalid slash amount")]
     InvalidSlashAmount,
+    #[msg("Invalid amount, must be greater than 0")]
+    InvalidAmount,
+    #[msg("Mint does not match the governance token")]
+    InvalidGovernanceMint,
+    #[msg("Token account is not owned by the signer")]
+    InvalidTokenAccount,
+    #[msg("No voting power at the proposal snapshot")]
+    NoVotingPower,
+    #[msg("Deposited governance tokens are insufficient")]
+    InsufficientDeposit,
+    #[msg("Governance tokens are locked until active votes end")]
+    TokensLocked,
+    #[msg("Quorum or approval threshold is invalid")]
+    InvalidThreshold,
+    #[msg("Timelock delay is invalid")]
+    InvalidTimelock,
+    #[msg("Guardian set is invalid")]
+    InvalidGuardianSet,
+    #[msg("Not enough guardian signatures")]
+    InsufficientGuardianApprovals,
+    #[msg("Proposal is not queued")]
+    ProposalNotQueued,
+    #[msg("Timelock has not expired")]
+    TimelockNotExpired,
+    #[msg("Timelock has already expired")]
+    TimelockExpired,
+    #[msg("Stake ceiling must not be below the stake floor")]
+    InvalidStakeRange,
+    #[msg("Proposal payload does not match its type")]
+    InvalidProposalPayload,
+    #[msg("Account required to execute this proposal type is missing")]
+    MissingGovernanceAccount,
+    #[msg("Instruction group is paused")]
+    ProgramPaused,
+    #[msg("Pause groups contain unknown bits")]
+    InvalidPauseGroups,
+    #[msg("Delegation is invalid")]
+    InvalidDelegation,
+    #[msg("Delegation does not apply to this proposal")]
+    DelegationNotApplicable,
+    #[msg("Delegation is neither revoked nor expired")]
+    DelegationStillActive,
+    #[msg("Delegate vote account is required to override the delegate")]
+    MissingDelegateVote,
+    #[msg("Too many delegations on a single vote")]
+    TooManyDelegations,
+    #[msg("Proposer does not hold enough governance tokens")]
+    InsufficientProposerBalance,
+    #[msg("Proposal already has votes")]
+    ProposalHasVotes,
+    #[msg("Conviction lock is invalid for this proposal")]
+    InvalidConviction,
+    #[msg("Upgrade accounts do not match the proposal")]
+    InvalidProgramUpgrade,
+    #[msg("Upgrade buffer does not match the approved hash")]
+    BufferHashMismatch,
+    #[msg("Model version must be newer than the current config")]
+    StaleModelVersion,
+    #[msg("Proposal instructions are invalid")]
+    InvalidProposalInstruction,
+    #[msg("Council members or threshold are invalid")]
+    InvalidCouncil,
+    #[msg("Signer is not a council member")]
+    NotCouncilMember,
+    #[msg("Council action does not match this instruction")]
+    CouncilActionMismatch,
+    #[msg("Council action has already been executed")]
+    CouncilActionExecuted,
+    #[msg("Member has already approved this council action")]
+    CouncilActionAlreadyApproved,
+    #[msg("Not enough council approvals")]
+    InsufficientCouncilApprovals,
+    #[msg("Signed vote batch is invalid")]
+    InvalidSignedVote,
+    #[msg("Voter has already voted on this proposal")]
+    AlreadySignedVote,
+    #[msg("Signed voting power does not match the snapshot")]
+    SignedVotePowerMismatch,
+    #[msg("Voter has too many open signed votes")]
+    TooManySignedVotes,
+    #[msg("Treasury spend is invalid")]
+    InvalidTreasurySpend,
+    #[msg("Treasury spend exceeds the period limit")]
+    TreasuryLimitExceeded,
+    #[msg("Treasury balance is too low")]
+    InsufficientTreasuryBalance,
+    #[msg("Treasury spend limit is invalid")]
+    InvalidSpendLimit,
+    #[msg("Proposal and analysis task accounts do not match")]
+    InvalidProposalLink,
+    #[msg("Consensus commitment does not match the inference results")]
+    InvalidConsensus,
+    #[msg("Not enough inference results agree with the consensus")]
+    ConsensusNotReached,
+    #[msg("Proposal can only be amended before analysis starts")]
+    ProposalNotAmendable,
+    #[msg("Proposal has reached the revision limit")]
+    TooManyRevisions,
+    #[msg("Node stake is insufficient")]
+    InsufficientStake,
+    #[msg("Signer is not authorized for this account")]
+    UnauthorizedActor,
+    #[msg("Remaining stake would fall below the node minimum")]
+    StakeBelowMinimum,
+    #[msg("No pending rewards to settle")]
+    InsufficientPendingRewards,
+    #[msg("Legacy node account is invalid")]
+    InvalidLegacyNode,
+    #[msg("Only the submitter of a task the node completed can rate it")]
+    RatingNotAllowed,
+    #[msg("Task has already been rated")]
+    AlreadyRated,
+    #[msg("Task requires commit-reveal submission")]
+    CommitRevealRequired,
+    #[msg("Task does not use commit-reveal submission")]
+    CommitRevealNotEnabled,
+    #[msg("Commit window has closed")]
+    CommitWindowClosed,
+    #[msg("Reveal window has not opened")]
+    RevealWindowNotOpen,
+    #[msg("Reveal window has closed")]
+    RevealWindowClosed,
+    #[msg("Reveal window is still open")]
+    RevealWindowOpen,
+    #[msg("Commitment has already been revealed")]
+    AlreadyRevealed,
+    #[msg("Revealed result does not match the commitment")]
+    InvalidReveal,
+    #[msg("Commit accounts do not match the task")]
+    InvalidCommitSet,
+    #[msg("Task already has its required number of nodes")]
+    TaskFullyAssigned,
+    #[msg("Agreeing or dissenting node accounts do not match the task")]
+    InvalidTaskNodeAccounts,
+    #[msg("Node claim has not timed out")]
+    ClaimNotExpired,
+    #[msg("Node already committed a result for this round")]
+    NodeAlreadyCommitted,
+    #[msg("Stake vault account is required")]
+    MissingStakeVault,
+    #[msg("Stake is locked by the unbonding cooldown or an open challenge")]
+    StakeLocked,
+    #[msg("Node has not retired")]
+    NodeNotRetired,
+    #[msg("Node still has bonded stake")]
+    StakeStillBonded,
+    #[msg("Node has unsettled rewards")]
+    UnsettledRewards,
+    #[msg("Proposal is analyzed by its linked TRO task")]
+    ProposalHasAnalysisTask,
+    #[msg("Proposal is not open for inference results")]
+    ProposalNotOpenForInference,
+    #[msg("Task result has already been verified")]
+    TaskAlreadyVerified,
+    #[msg("Invalid task timeout or offline penalty parameters")]
+    InvalidLivenessParams,
+    #[msg("Delegation is counted on a proposal that is still open")]
+    DelegationInUse,
 }
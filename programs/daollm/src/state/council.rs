@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::state::ResolutionOutcome;
+
+pub const COUNCIL_SEED: &[u8] = b"council";
+pub const COUNCIL_ACTION_SEED: &[u8] = b"council-action";
+pub const MAX_COUNCIL_MEMBERS: usize = 9;
+
+#[account]
+pub struct Council {
+    pub members: Vec<Pubkey>, // 理事会成员
+    pub threshold: u8,        // 执行操作所需的批准数
+    pub rotated_at: i64,      // 最近一次轮换成员的时间
+    pub bump: u8,
+}
+
+impl Council {
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        4 + MAX_COUNCIL_MEMBERS * 32 + // members
+        1 + // threshold
+        8 + // rotated_at
+        1; // bump
+
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CouncilActionKind {
+    // 裁决任务挑战
+    ResolveChallenge {
+        task_id: u64,
+        challenger: Pubkey,
+        resolution: ResolutionOutcome,
+    },
+    // 罚没节点质押，可同时暂停节点
+    SlashNode {
+        node_owner: Pubkey,
+        amount: u64,
+        suspend: bool,
+    },
+    // 重新计算节点的动态最低质押
+    UpdateDynamicStake {
+        node_owner: Pubkey,
+    },
+    // 结算节点奖励，不超过 max_amount
+    SettleReward {
+        node_owner: Pubkey,
+        max_amount: u64,
+    },
+}
+
+impl CouncilActionKind {
+    pub const MAX_SIZE: usize = 1 + 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CouncilApproval {
+    pub member: Pubkey,   // 批准的成员
+    pub approved_at: i64, // 批准时间
+}
+
+impl CouncilApproval {
+    pub const SIZE: usize = 32 + 8;
+}
+
+#[account]
+pub struct CouncilAction {
+    pub action_id: u64,                  // 操作编号
+    pub proposer: Pubkey,                // 发起成员
+    pub action: CouncilActionKind,       // 待执行的操作及参数
+    pub approvals: Vec<CouncilApproval>, // 成员批准记录（含发起人）
+    pub created_at: i64,                 // 创建时间
+    pub executed_at: i64,                // 执行时间，未执行为 0
+    pub bump: u8,
+}
+
+impl CouncilAction {
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        8 + // action_id
+        32 + // proposer
+        CouncilActionKind::MAX_SIZE + // action
+        4 + MAX_COUNCIL_MEMBERS * CouncilApproval::SIZE + // approvals
+        8 + // created_at
+        8 + // executed_at
+        1; // bump
+
+    /// Approvals from members that still sit on the council.
+    pub fn approvals_from(&self, council: &Council) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approval| council.is_member(&approval.member))
+            .count()
+    }
+}
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use super::tro::CONSENSUS_THRESHOLD_BPS;
+
 #[account]
 pub struct ModelConfig {
     pub model_version: u64,      // 模型版本号
@@ -24,12 +26,51 @@ impl ModelConfig {
         32; // updated_by
 }
 
+pub const MODEL_CONFIG_SEED: &[u8] = b"model-config";
+pub const MODEL_CONFIG_HISTORY_SEED: &[u8] = b"model-config-history";
+pub const MAX_MODEL_CONFIG_HISTORY: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ModelConfigEntry {
+    pub config: ModelConfig, // 当时生效的配置
+    pub proposal: Pubkey,    // 产生该配置的提案（初始化时为默认值）
+}
+
+impl ModelConfigEntry {
+    pub const SIZE: usize = (ModelConfig::MAX_SIZE - 8) + 32;
+}
+
+#[account]
+pub struct ModelConfigHistory {
+    pub entries: Vec<ModelConfigEntry>, // 最近的配置，按时间从旧到新
+    pub bump: u8,
+}
+
+impl ModelConfigHistory {
+    pub const MAX_SIZE: usize = 8 +     // discriminator
+        4 + MAX_MODEL_CONFIG_HISTORY * ModelConfigEntry::SIZE + // entries (Vec)
+        1; // bump
+
+    /// Appends a configuration, dropping the oldest once the buffer is full.
+    pub fn record(&mut self, config: ModelConfig, proposal: Pubkey) {
+        if self.entries.len() >= MAX_MODEL_CONFIG_HISTORY {
+            self.entries.remove(0);
+        }
+        self.entries.push(ModelConfigEntry { config, proposal });
+    }
+}
+
 #[account]
 pub struct GovernanceProposal {
     pub proposal_id: u64,                      // 提案ID
     pub proposer: Pubkey,                      // 提案者
     pub proposal_type: GovernanceProposalType, // 提案类型
     pub target_config: Option<ModelConfig>,    // 目标配置（如果修改配置）
+    pub reward_rate_update: Option<RewardRateUpdate>, // 奖励率更新参数
+    pub node_stake_update: Option<NodeStakeUpdate>,   // 节点质押区间更新参数
+    pub pause_update: Option<PauseUpdate>,            // 紧急暂停参数
+    pub program_upgrade: Option<ProgramUpgrade>,      // 程序升级参数
+    pub treasury_spend: Option<TreasurySpend>,        // 国库支出参数
     pub description: String,                   // 提案描述
     pub votes_for: u64,                        // 支持票数
     pub votes_against: u64,                    // 反对票数
@@ -38,6 +79,15 @@ pub struct GovernanceProposal {
     pub created_at: i64,                       // 创建时间
     pub voting_ends_at: i64,                   // 投票结束时间
     pub executed_at: Option<i64>,              // 执行时间
+    pub snapshot_slot: u64,                    // 投票权快照slot
+    pub snapshot_supply: u64,                  // 快照时的代币总供应量
+    pub quorum_bps: u16,                       // 创建时锁定的法定人数
+    pub approval_threshold_bps: u16,           // 创建时锁定的通过阈值
+    pub timelock_secs: i64,                    // 创建时锁定的执行延迟
+    pub eta: i64,                              // 排队后最早可执行时间
+    pub deposit_amount: u64,                   // 托管在提案账户中的押金（lamports）
+    pub first_vote_at: i64,                    // 首次投票时间（0表示尚无投票）
+    pub vote_strategy: VoteStrategy,           // 计票方式
 }
 
 impl GovernanceProposal {
@@ -46,6 +96,11 @@ impl GovernanceProposal {
         32 +                             // proposer
         1 +                              // proposal_type
         1 + 8 + ModelConfig::MAX_SIZE + // target_config (Option)
+        1 + RewardRateUpdate::SIZE +     // reward_rate_update (Option)
+        1 + NodeStakeUpdate::SIZE +      // node_stake_update (Option)
+        1 + PauseUpdate::SIZE +          // pause_update (Option)
+        1 + ProgramUpgrade::SIZE +       // program_upgrade (Option)
+        1 + TreasurySpend::SIZE +        // treasury_spend (Option)
         4 + 256 +                        // description (String)
         8 +                              // votes_for
         8 +                              // votes_against
@@ -53,7 +108,77 @@ impl GovernanceProposal {
         1 +                              // status
         8 +                              // created_at
         8 +                              // voting_ends_at
-        1 + 8; // executed_at (Option<i64>)
+        1 + 8 +                          // executed_at (Option<i64>)
+        8 +                              // snapshot_slot
+        8 +                              // snapshot_supply
+        2 +                              // quorum_bps
+        2 +                              // approval_threshold_bps
+        8 +                              // timelock_secs
+        8 +                              // eta
+        8 +                              // deposit_amount
+        8 +                              // first_vote_at
+        1; // vote_strategy
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct RewardRateUpdate {
+    pub base_reward_rate_bps: u16,     // 基础奖励率
+    pub high_perf_multiplier_bps: u16, // 高性能节点倍率
+    pub low_perf_penalty_bps: u16,     // 低性能节点惩罚
+}
+
+impl RewardRateUpdate {
+    pub const SIZE: usize = 2 + 2 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct NodeStakeUpdate {
+    pub stake_floor: u64,                  // 最低质押
+    pub stake_ceiling: u64,                // 最高质押
+    pub task_timeout_secs: i64,            // 任务认领超时
+    pub offline_grace_secs: i64,           // 离线宽限期
+    pub offline_penalty_bps_per_hour: u16, // 超出宽限期后每小时罚没比例
+}
+
+impl NodeStakeUpdate {
+    pub const SIZE: usize = 8 + 8 + 8 + 8 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct PauseUpdate {
+    pub paused: bool,      // 全局暂停
+    pub paused_groups: u8, // 暂停的指令组（PAUSE_* 位掩码）
+}
+
+impl PauseUpdate {
+    pub const SIZE: usize = 1 + 1;
+}
+
+/// Upgrade payload. `buffer_hash` is the SHA-256 of the buffer's program bytes,
+/// i.e. the account data after the loader's buffer metadata. `spill` receives
+/// the buffer's lamports, so it is voted on rather than left to the executor.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ProgramUpgrade {
+    pub buffer: Pubkey,        // 已写入新程序的缓冲账户
+    pub buffer_hash: [u8; 32], // 缓冲区程序数据的预期哈希
+    pub spill: Pubkey,         // 升级后接收缓冲区租金的账户
+}
+
+impl ProgramUpgrade {
+    pub const SIZE: usize = 32 + 32 + 32;
+}
+
+/// Treasury payout. For SPL tokens `recipient` is the wallet that must own the
+/// destination token account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct TreasurySpend {
+    pub recipient: Pubkey,    // 收款钱包
+    pub amount: u64,          // 支出数量（lamports或代币最小单位）
+    pub mint: Option<Pubkey>, // 代币mint（None表示SOL）
+}
+
+impl TreasurySpend {
+    pub const SIZE: usize = 32 + 8 + 1 + 32;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -63,6 +188,51 @@ pub enum GovernanceProposalType {
     UpdateNodeStake,   // 更新节点质押要求
     EmergencyPause,    // 紧急暂停
     UpgradeProgram,    // 升级程序
+    Custom,            // 通过CPI执行任意指令
+    TreasurySpend,     // 国库支出
+}
+
+pub const PROPOSAL_INSTRUCTIONS_SEED: &[u8] = b"proposal-instructions";
+pub const MAX_PROPOSAL_INSTRUCTIONS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ProposalAccountMeta {
+    pub pubkey: Pubkey,    // 账户地址
+    pub is_signer: bool,   // 是否需要签名（仅限治理PDA）
+    pub is_writable: bool, // 是否可写
+}
+
+impl ProposalAccountMeta {
+    pub const SIZE: usize = 32 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ProposalInstruction {
+    pub program_id: Pubkey,                 // 目标程序
+    pub accounts: Vec<ProposalAccountMeta>, // 账户列表
+    pub data: Vec<u8>,                      // 序列化的指令数据
+}
+
+impl ProposalInstruction {
+    pub fn size(&self) -> usize {
+        32 + 4 + self.accounts.len() * ProposalAccountMeta::SIZE + 4 + self.data.len()
+    }
+}
+
+#[account]
+pub struct ProposalInstructions {
+    pub proposal: Pubkey,                       // 所属提案
+    pub instructions: Vec<ProposalInstruction>, // 通过后按顺序执行的指令
+    pub bump: u8,
+}
+
+impl ProposalInstructions {
+    pub fn space_for(instructions: &[ProposalInstruction]) -> usize {
+        8 +                              // discriminator
+        32 +                             // proposal
+        4 + instructions.iter().map(ProposalInstruction::size).sum::<usize>() + // instructions (Vec)
+        1 // bump
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -72,6 +242,8 @@ pub enum GovernanceProposalStatus {
     Rejected,  // 已拒绝
     Executed,  // 已执行
     Cancelled, // 已取消
+    Queued,    // 时间锁排队中
+    Vetoed,    // 被守护者否决
 }
 
 #[account]
@@ -81,6 +253,12 @@ pub struct Vote {
     pub vote_type: GovernanceVoteType, // 投票类型
     pub voting_power: u64,             // 投票权重（基于代币数量）
     pub timestamp: i64,                // 投票时间
+    pub snapshot_slot: u64,            // 计算投票权所用的快照slot
+    pub token_account: Pubkey,         // 支撑投票权的代币托管账户
+    pub delegated_power: u64,          // 其中来自委托的投票权
+    pub delegated_votes: Vec<DelegatedVote>, // 计入本票的委托人
+    pub weight: u64,                   // 按提案计票方式折算后的票数
+    pub conviction_periods: u8,        // 信念投票额外锁定的周期数
 }
 
 impl Vote {
@@ -89,7 +267,66 @@ impl Vote {
         8 +                              // proposal_id
         1 +                              // vote_type
         8 +                              // voting_power
-        8; // timestamp
+        8 +                              // timestamp
+        8 +                              // snapshot_slot
+        32 +                             // token_account
+        8 +                              // delegated_power
+        4 + MAX_DELEGATED_VOTES * DelegatedVote::SIZE + // delegated_votes (Vec)
+        8 +                              // weight
+        1; // conviction_periods
+}
+
+pub const DELEGATION_SEED: &[u8] = b"delegation";
+pub const MAX_DELEGATED_VOTES: usize = 16;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct DelegatedVote {
+    pub delegator: Pubkey, // 委托人
+    pub voting_power: u64, // 委托人在快照时的投票权
+    pub weight: u64,       // 折算后的票数
+}
+
+impl DelegatedVote {
+    pub const SIZE: usize = 32 + 8 + 8;
+}
+
+#[account]
+pub struct Delegation {
+    pub delegator: Pubkey,                     // 委托人
+    pub delegate: Pubkey,                      // 受托人
+    pub scope: Option<GovernanceProposalType>, // 仅限某类提案（None表示全部）
+    pub expires_at: Option<i64>,               // 过期时间（None表示长期有效）
+    pub created_slot: u64,                     // 委托创建slot
+    pub revoked_slot: Option<u64>,             // 撤销slot
+    pub locked_until: i64,                     // 计入此委托的提案中最晚的投票结束时间
+    pub bump: u8,
+}
+
+impl Delegation {
+    pub const MAX_SIZE: usize = 8 +     // discriminator
+        32 +                             // delegator
+        32 +                             // delegate
+        1 + 1 +                          // scope (Option)
+        1 + 8 +                          // expires_at (Option)
+        8 +                              // created_slot
+        1 + 8 +                          // revoked_slot (Option)
+        8 +                              // locked_until
+        1; // bump
+
+    /// Whether the delegation counts for a proposal of `proposal_type` snapshotted
+    /// at `snapshot_slot` / `snapshot_ts`. Delegations, revocations and expiry only
+    /// affect proposals created after them.
+    pub fn applies_to(
+        &self,
+        proposal_type: &GovernanceProposalType,
+        snapshot_slot: u64,
+        snapshot_ts: i64,
+    ) -> bool {
+        self.created_slot < snapshot_slot
+            && self.revoked_slot.is_none_or(|slot| slot >= snapshot_slot)
+            && self.expires_at.is_none_or(|expires_at| snapshot_ts < expires_at)
+            && self.scope.as_ref().is_none_or(|scope| scope == proposal_type)
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
@@ -98,3 +335,271 @@ pub enum GovernanceVoteType {
     Against, // 反对
     Abstain, // 弃权
 }
+
+// 链下签名投票：选民对 DOMAIN || borsh(SignedVoteMessage) 签名，由中继者批量上链
+pub const SIGNED_VOTE_DOMAIN: &[u8] = b"daollm-signed-vote";
+pub const MAX_SIGNED_VOTE_RECEIPTS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct SignedVoteMessage {
+    pub proposal: Pubkey,              // 提案账户地址
+    pub proposal_id: u64,              // 提案ID
+    pub vote_type: GovernanceVoteType, // 投票类型
+    pub voting_power: u64,             // 签名时的快照投票权
+}
+
+impl SignedVoteMessage {
+    pub fn decode(message: &[u8]) -> Option<Self> {
+        let body = message.strip_prefix(SIGNED_VOTE_DOMAIN)?;
+        Self::try_from_slice(body).ok()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct SignedVoteReceipt {
+    pub proposal: Pubkey,    // 已计入签名投票的提案
+    pub voting_ends_at: i64, // 提案投票结束时间，之后回执可清理
+}
+
+impl SignedVoteReceipt {
+    pub const SIZE: usize = 32 + 8;
+}
+
+pub const CONVICTION_PERIOD_SECS: i64 = 7 * 24 * 3_600;
+pub const MAX_CONVICTION_PERIODS: u8 = 6;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub enum VoteStrategy {
+    Linear,     // 按代币数量线性计票
+    Quadratic,  // 二次方计票：票数为代币数量的平方根
+    Conviction, // 信念投票：额外锁定越久权重越高
+}
+
+impl VoteStrategy {
+    /// Counting weight of `voting_power` tokens. `conviction_periods` is the
+    /// number of extra lock periods and only matters for conviction voting.
+    pub fn weight(&self, voting_power: u64, conviction_periods: u8) -> u64 {
+        match self {
+            VoteStrategy::Linear => voting_power,
+            VoteStrategy::Quadratic => integer_sqrt(voting_power),
+            VoteStrategy::Conviction => {
+                voting_power.saturating_mul(1 + conviction_periods.min(MAX_CONVICTION_PERIODS) as u64)
+            }
+        }
+    }
+}
+
+fn integer_sqrt(value: u64) -> u64 {
+    let value = value as u128;
+    let mut root = value;
+    let mut next = root.div_ceil(2);
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+    root as u64
+}
+
+pub const MAX_VOTING_POWER_CHECKPOINTS: usize = 16;
+pub const MAX_PROPOSAL_TYPES: usize = 8;
+pub const DEFAULT_QUORUM_BPS: u16 = 1_000;
+// 与任务共识使用同一个三分之二阈值（economy-params.json 中四舍五入为 67%）
+pub const DEFAULT_APPROVAL_THRESHOLD_BPS: u16 = CONSENSUS_THRESHOLD_BPS;
+pub const DEFAULT_TIMELOCK_SECS: i64 = 2 * 24 * 3_600;
+// 程序升级与模型配置变更至少保留一天，给守护者否决和用户退出留出时间
+pub const MIN_CRITICAL_TIMELOCK_SECS: i64 = 24 * 3_600;
+pub const MAX_GUARDIANS: usize = 7;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ProposalThreshold {
+    pub proposal_type: GovernanceProposalType, // 提案类型
+    pub quorum_bps: u16,                       // 最低投票量（占快照供应量）
+    pub approval_threshold_bps: u16,           // 支持票占比阈值
+}
+
+impl ProposalThreshold {
+    pub const SIZE: usize = 1 + 2 + 2;
+
+    pub fn default_for(proposal_type: GovernanceProposalType) -> Self {
+        Self {
+            proposal_type,
+            quorum_bps: DEFAULT_QUORUM_BPS,
+            approval_threshold_bps: DEFAULT_APPROVAL_THRESHOLD_BPS,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ProposalTimelock {
+    pub proposal_type: GovernanceProposalType, // 提案类型
+    pub delay_secs: i64,                       // 通过后到可执行的延迟
+}
+
+impl ProposalTimelock {
+    pub const SIZE: usize = 1 + 8;
+
+    pub fn min_delay_for(proposal_type: &GovernanceProposalType) -> i64 {
+        match proposal_type {
+            GovernanceProposalType::UpgradeProgram | GovernanceProposalType::UpdateModelConfig => {
+                MIN_CRITICAL_TIMELOCK_SECS
+            }
+            _ => 0,
+        }
+    }
+}
+
+#[account]
+pub struct GovernanceConfig {
+    pub authority: Pubkey,                  // 配置管理者
+    pub governance_mint: Pubkey,            // DAO治理代币
+    pub thresholds: Vec<ProposalThreshold>, // 各提案类型的法定人数与通过阈值
+    pub timelocks: Vec<ProposalTimelock>,   // 各提案类型的执行延迟
+    pub guardians: Vec<Pubkey>,             // 守护者多签成员
+    pub guardian_threshold: u8,             // 守护者多签所需签名数
+    pub proposal_deposit: u64,              // 创建提案需托管的押金（lamports）
+    pub min_proposer_balance: u64,          // 提案者最低托管代币数量
+    pub bump: u8,
+}
+
+impl GovernanceConfig {
+    pub const MAX_SIZE: usize = 8 +     // discriminator
+        32 +                             // authority
+        32 +                             // governance_mint
+        4 + MAX_PROPOSAL_TYPES * ProposalThreshold::SIZE + // thresholds (Vec)
+        4 + MAX_PROPOSAL_TYPES * ProposalTimelock::SIZE + // timelocks (Vec)
+        4 + MAX_GUARDIANS * 32 +         // guardians (Vec)
+        1 +                              // guardian_threshold
+        8 +                              // proposal_deposit
+        8 +                              // min_proposer_balance
+        1; // bump
+
+    pub fn threshold_for(&self, proposal_type: &GovernanceProposalType) -> ProposalThreshold {
+        self.thresholds
+            .iter()
+            .find(|threshold| &threshold.proposal_type == proposal_type)
+            .cloned()
+            .unwrap_or_else(|| ProposalThreshold::default_for(proposal_type.clone()))
+    }
+
+    pub fn timelock_for(&self, proposal_type: &GovernanceProposalType) -> i64 {
+        self.timelocks
+            .iter()
+            .find(|timelock| &timelock.proposal_type == proposal_type)
+            .map(|timelock| timelock.delay_secs)
+            .unwrap_or(DEFAULT_TIMELOCK_SECS)
+    }
+
+    /// Counts distinct guardians among the given signers.
+    pub fn guardian_approvals<'a>(&self, signers: impl Iterator<Item = &'a Pubkey>) -> usize {
+        let mut approved: Vec<&Pubkey> = Vec::new();
+        for signer in signers {
+            if self.guardians.contains(signer) && !approved.contains(&signer) {
+                approved.push(signer);
+            }
+        }
+        approved.len()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct VotingPowerCheckpoint {
+    pub slot: u64,   // 余额变化所在slot
+    pub amount: u64, // 变化后的托管余额
+}
+
+#[account]
+pub struct VoterRecord {
+    pub voter: Pubkey,                             // 投票者
+    pub governance_mint: Pubkey,                   // DAO治理代币
+    pub escrow: Pubkey,                            // 代币托管账户
+    pub deposited_amount: u64,                     // 当前托管数量
+    pub checkpoints: Vec<VotingPowerCheckpoint>,   // 托管余额历史（用于快照）
+    pub locked_until: i64,                         // 已投票提案结束前不可提取
+    pub signed_votes: Vec<SignedVoteReceipt>,      // 投票中提案的签名投票回执（防重复计票）
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl VoterRecord {
+    pub const MAX_SIZE: usize = 8 +     // discriminator
+        32 +                             // voter
+        32 +                             // governance_mint
+        32 +                             // escrow
+        8 +                              // deposited_amount
+        4 + MAX_VOTING_POWER_CHECKPOINTS * 16 + // checkpoints (Vec)
+        8 +                              // locked_until
+        4 + MAX_SIGNED_VOTE_RECEIPTS * SignedVoteReceipt::SIZE + // signed_votes (Vec)
+        1 +                              // bump
+        1; // escrow_bump
+
+    /// Voting power held strictly before `snapshot_slot`, capped by the current deposit so
+    /// withdrawn tokens never count. Returns 0 if the snapshot predates the retained history.
+    pub fn power_at(&self, snapshot_slot: u64) -> u64 {
+        self.checkpoints
+            .iter()
+            .rev()
+            .find(|checkpoint| checkpoint.slot < snapshot_slot)
+            .map(|checkpoint| checkpoint.amount.min(self.deposited_amount))
+            .unwrap_or(0)
+    }
+
+    pub fn record_checkpoint(&mut self, slot: u64) {
+        let amount = self.deposited_amount;
+        match self.checkpoints.last_mut() {
+            Some(last) if last.slot == slot => last.amount = amount,
+            _ => {
+                if self.checkpoints.len() >= MAX_VOTING_POWER_CHECKPOINTS {
+                    self.checkpoints.remove(0);
+                }
+                self.checkpoints.push(VotingPowerCheckpoint { slot, amount });
+            }
+        }
+    }
+
+    pub fn has_signed_vote(&self, proposal: &Pubkey) -> bool {
+        self.signed_votes.iter().any(|receipt| receipt.proposal == *proposal)
+    }
+
+    /// Records a relayed signed vote, dropping receipts for proposals whose voting
+    /// has ended. Returns false when too many proposals are still open.
+    pub fn record_signed_vote(&mut self, proposal: Pubkey, voting_ends_at: i64, now: i64) -> bool {
+        self.signed_votes.retain(|receipt| receipt.voting_ends_at > now);
+        if self.signed_votes.len() >= MAX_SIGNED_VOTE_RECEIPTS {
+            return false;
+        }
+        self.signed_votes.push(SignedVoteReceipt { proposal, voting_ends_at });
+        true
+    }
+}
+
+// 持有程序升级权限的治理PDA
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"governance-authority";
+
+pub const PROGRAM_STATE_SEED: &[u8] = b"program-state";
+pub const PAUSE_TASKS: u8 = 1 << 0;
+pub const PAUSE_STAKING: u8 = 1 << 1;
+pub const PAUSE_REWARDS: u8 = 1 << 2;
+pub const PAUSE_GOVERNANCE: u8 = 1 << 3;
+pub const PAUSE_ALL_GROUPS: u8 = PAUSE_TASKS | PAUSE_STAKING | PAUSE_REWARDS | PAUSE_GOVERNANCE;
+
+#[account]
+pub struct ProgramState {
+    pub paused: bool,      // 全局暂停标志
+    pub paused_groups: u8, // 按指令组暂停（PAUSE_* 位掩码）
+    pub paused_at: i64,    // 最近一次暂停时间
+    pub paused_by: Pubkey, // 最近一次暂停的发起者（守护者或治理提案）
+    pub bump: u8,
+}
+
+impl ProgramState {
+    pub const MAX_SIZE: usize = 8 +     // discriminator
+        1 +                              // paused
+        1 +                              // paused_groups
+        8 +                              // paused_at
+        32 +                             // paused_by
+        1; // bump
+
+    pub fn is_paused(&self, group: u8) -> bool {
+        self.paused || self.paused_groups & group != 0
+    }
+}
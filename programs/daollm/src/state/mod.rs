@@ -1,11 +1,15 @@
+pub mod council;
 pub mod governance;
 pub mod node;
 pub mod proposal;
 pub mod training;
+pub mod treasury;
 pub mod tro;
 
+pub use council::*;
 pub use governance::*;
 pub use node::*;
 pub use proposal::*;
 pub use training::*;
+pub use treasury::*;
 pub use tro::*;
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use solana_sha256_hasher::hashv;
 
 use super::tro::WorkflowClass;
 
@@ -6,6 +7,9 @@ pub const PROPOSAL_ID_MAX_LEN: usize = 64;
 pub const RESULT_HASH_MAX_LEN: usize = 64;
 pub const METADATA_HASH_MAX_LEN: usize = 64;
 pub const IPFS_CID_MAX_LEN: usize = 128;
+pub const LEGACY_NODE_SEED: &[u8] = b"node";
+pub const REPUTATION_EMA_ALPHA_BPS: u32 = 2_000; // weight of the newest sample
+const BPS_DENOMINATOR: u32 = 10_000;
 
 #[account]
 pub struct ReasoningNode {
@@ -34,10 +38,13 @@ pub struct ReasoningNode {
     pub dynamic_multiplier_bps: u16,
     pub last_settlement_ts: i64,
     pub stake_vault_bump: u8,
+    pub unbonding_amount: u64,
+    pub unbond_available_ts: i64,
+    pub challenge_exposure_until: i64,
+    pub open_challenges: u16,
+    pub claimed_ts: i64,
 }
 
-pub type InferenceNode = ReasoningNode;
-
 impl ReasoningNode {
     pub const MAX_SIZE: usize = 8 + // discriminator
         32 + // owner
@@ -64,11 +71,103 @@ impl ReasoningNode {
         8 + // last_reward_slot
         2 + // dynamic_multiplier_bps
         8 + // last_settlement_ts
-        1; // stake_vault_bump
+        1 + // stake_vault_bump
+        8 + // unbonding_amount
+        8 + // unbond_available_ts
+        8 + // challenge_exposure_until
+        2 + // open_challenges
+        8; // claimed_ts
+
+    /// Folds a verification score into the verification rate and reputation.
+    pub fn record_verification(&mut self, score_bps: u16) {
+        self.verification_success_rate_bps = ema_bps(self.verification_success_rate_bps, score_bps);
+        self.reputation_score_bps = ema_bps(self.reputation_score_bps, score_bps);
+    }
+
+    /// An upheld result counts as a perfect sample, an overturned one as zero.
+    pub fn record_challenge_outcome(&mut self, upheld: bool) {
+        let sample = if upheld { BPS_DENOMINATOR as u16 } else { 0 };
+        self.verification_success_rate_bps = ema_bps(self.verification_success_rate_bps, sample);
+        self.reputation_score_bps = ema_bps(self.reputation_score_bps, sample);
+    }
+
+    /// A node whose result lost the quorum on a task that was then finalized.
+    pub fn record_dissent(&mut self) {
+        self.verification_success_rate_bps = ema_bps(self.verification_success_rate_bps, 0);
+        self.reputation_score_bps = ema_bps(self.reputation_score_bps, 0);
+    }
+
+    /// Folds whether a finalized task was delivered within its workflow budget
+    /// into throughput and reputation.
+    pub fn record_finalization(&mut self, on_time: bool) {
+        let sample = if on_time { BPS_DENOMINATOR as u16 } else { 0 };
+        self.throughput_score_bps = ema_bps(self.throughput_score_bps, sample);
+        self.reputation_score_bps = ema_bps(self.reputation_score_bps, sample);
+    }
+
+    /// Folds a task submitter's rating into reputation.
+    pub fn record_rating(&mut self, rating_bps: u16) {
+        self.reputation_score_bps = ema_bps(self.reputation_score_bps, rating_bps);
+    }
+
+    /// Takes a slash from active stake first, then from stake still unbonding.
+    pub fn apply_slash(&mut self, amount: u64) {
+        let from_stake = amount.min(self.stake_amount);
+        self.stake_amount -= from_stake;
+        self.unbonding_amount = self.unbonding_amount.saturating_sub(amount - from_stake);
+        self.pending_slash_amount = self.pending_slash_amount.saturating_add(amount);
+    }
+
+    /// Keeps stake locked until a challenge window on one of the node's results
+    /// has closed.
+    pub fn extend_challenge_exposure(&mut self, until: i64) {
+        self.challenge_exposure_until = self.challenge_exposure_until.max(until);
+    }
+
+    /// Whether unbonded stake can leave the vault at `now`.
+    pub fn stake_unlocked(&self, now: i64) -> bool {
+        now >= self.unbond_available_ts
+            && now > self.challenge_exposure_until
+            && self.open_challenges == 0
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+/// Exponential moving average in basis points, weighting the new sample by
+/// `REPUTATION_EMA_ALPHA_BPS`.
+fn ema_bps(current: u16, sample: u16) -> u16 {
+    let sample = (sample as u32).min(BPS_DENOMINATOR);
+    let current = (current as u32).min(BPS_DENOMINATOR);
+    ((current * (BPS_DENOMINATOR - REPUTATION_EMA_ALPHA_BPS) + sample * REPUTATION_EMA_ALPHA_BPS)
+        / BPS_DENOMINATOR) as u16
+}
+
+/// Node account written by the original `register_node` under `LEGACY_NODE_SEED`.
+/// Only read by `migrate_legacy_node`, which moves it onto a `ReasoningNode`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyInferenceNode {
+    pub owner: Pubkey,
+    pub stake_amount: u64,
+    pub reputation_score: u8, // 0-100
+    pub total_inferences: u64,
+    pub is_active: bool,
+}
+
+impl LegacyInferenceNode {
+    /// Decodes the account data, checking the discriminator of the old
+    /// `InferenceNode` account type.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        let discriminator = hashv(&[b"account:InferenceNode"]).to_bytes();
+        require!(
+            data.len() > 8 && data[..8] == discriminator[..8],
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ModelCapability {
+    #[default]
     Local7B,
     Local13B,
     Local70B,
@@ -77,14 +176,9 @@ pub enum ModelCapability {
     Specialist,
 }
 
-impl Default for ModelCapability {
-    fn default() -> Self {
-        ModelCapability::Local7B
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum NodeLifecycleStatus {
+    #[default]
     Registered,
     Active,
     Suspended,
@@ -92,12 +186,6 @@ pub enum NodeLifecycleStatus {
     Retired,
 }
 
-impl Default for NodeLifecycleStatus {
-    fn default() -> Self {
-        NodeLifecycleStatus::Registered
-    }
-}
-
 #[account]
 pub struct InferenceResult {
     pub proposal_id: String,
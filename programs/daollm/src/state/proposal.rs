@@ -1,21 +1,51 @@
 use anchor_lang::prelude::*;
 
+use super::node::RESULT_HASH_MAX_LEN;
+use super::tro::{TaskCriticality, WorkflowClass};
+
+pub const PROPOSAL_IPFS_HASH_MAX_LEN: usize = 64;
+pub const MAX_PROPOSAL_REVISIONS: usize = 8;
+
 #[account]
 pub struct Proposal {
-    pub submitter: Pubkey,      // 提交者公钥
-    pub proposal_id: String,    // 提案ID
-    pub ipfs_hash: String,      // IPFS内容哈希
-    pub timestamp: i64,         // 提交时间戳
-    pub status: ProposalStatus, // 提案状态
+    pub submitter: Pubkey,             // 提交者公钥
+    pub proposal_id: String,           // 提案ID
+    pub ipfs_hash: String,             // IPFS内容哈希
+    pub timestamp: i64,                // 提交时间戳
+    pub status: ProposalStatus,        // 提案状态
+    pub analysis_task: Option<Pubkey>, // 关联的TRO分析任务
+    pub inference_count: u16,          // 已提交的推理结果数
+    pub consensus_hash: String,        // 共识结果哈希
+    pub contributors_root: [u8; 32],   // 贡献节点InferenceResult PDA的Merkle根
+    pub agreement_bps: u16,            // 与共识一致的结果占比
+    pub revisions: Vec<ProposalRevision>, // 修订历史（第0版为初始提交）
 }
 
 impl Proposal {
     pub const MAX_SIZE: usize = 8 +  // discriminator
         32 +                         // submitter
         4 + 64 +                     // proposal_id (String)
-        4 + 64 +                     // ipfs_hash (String)
+        4 + PROPOSAL_IPFS_HASH_MAX_LEN + // ipfs_hash (String)
         8 +                          // timestamp (i64)
-        1; // status (enum)
+        1 +                          // status (enum)
+        1 + 32 +                     // analysis_task (Option<Pubkey>)
+        2 +                          // inference_count
+        4 + RESULT_HASH_MAX_LEN +    // consensus_hash (String)
+        32 +                         // contributors_root
+        2 +                          // agreement_bps
+        4 + MAX_PROPOSAL_REVISIONS * ProposalRevision::SIZE; // revisions (Vec)
+}
+
+// 提案修订记录
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalRevision {
+    pub revision: u16,     // 修订号
+    pub ipfs_hash: String, // 该版本的IPFS内容哈希
+    pub timestamp: i64,    // 修订时间戳
+}
+
+impl ProposalRevision {
+    pub const SIZE: usize = 2 + (4 + PROPOSAL_IPFS_HASH_MAX_LEN) + 8;
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
@@ -24,3 +54,15 @@ pub enum ProposalStatus {
     Analyzing, // 分析中
     Completed, // 分析完成
 }
+
+// 提交提案时一并开启的治理类TRO分析任务参数
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProposalAnalysis {
+    pub task_id: u64,                 // TRO任务ID
+    pub workflow: WorkflowClass,      // 期望的推理流程
+    pub criticality: TaskCriticality, // 任务重要程度
+    pub complexity_score: u16,        // 复杂度评分
+    pub stake_pool: u64,              // 预存的分析奖励（lamports）
+    pub min_node_stake: u64,          // 节点最低质押
+    pub challenge_window_secs: i64,   // 挑战期（秒）
+}
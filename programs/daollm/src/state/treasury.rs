@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const MAX_SPEND_LIMITS: usize = 8;
+
+/// Cap on what executed proposals may pay out of one asset per period. `mint`
+/// is `None` for SOL.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct SpendLimit {
+    pub mint: Option<Pubkey>, // 资产代币，None 表示 SOL
+    pub max_per_period: u64,  // 每周期支出上限
+    pub period_secs: i64,     // 周期长度（秒）
+    pub period_start: i64,    // 当前周期开始时间
+    pub spent_in_period: u64, // 当前周期已支出
+}
+
+impl SpendLimit {
+    pub const SIZE: usize = 1 + 32 + // mint (Option)
+        8 + // max_per_period
+        8 + // period_secs
+        8 + // period_start
+        8; // spent_in_period
+}
+
+/// Holds SOL in its own lamports and SPL tokens in token accounts it owns.
+#[account]
+pub struct Treasury {
+    pub spend_limits: Vec<SpendLimit>, // 各资产的支出额度
+    pub total_swept: u64,              // 累计归集的 SOL
+    pub bump: u8,
+}
+
+impl Treasury {
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        4 + MAX_SPEND_LIMITS * SpendLimit::SIZE + // spend_limits
+        8 + // total_swept
+        1; // bump
+
+    /// Charges `amount` against the limit for `mint`, starting a new period once
+    /// the current one has elapsed. Assets without a configured limit are uncapped.
+    pub fn record_spend(&mut self, mint: Option<Pubkey>, amount: u64, now: i64) -> bool {
+        let Some(limit) = self.spend_limits.iter_mut().find(|limit| limit.mint == mint) else {
+            return true;
+        };
+        if now >= limit.period_start.saturating_add(limit.period_secs) {
+            limit.period_start = now;
+            limit.spent_in_period = 0;
+        }
+        match limit.spent_in_period.checked_add(amount) {
+            Some(spent) if spent <= limit.max_per_period => {
+                limit.spent_in_period = spent;
+                true
+            }
+            _ => false,
+        }
+    }
+}
@@ -4,6 +4,10 @@ use super::node::ModelCapability;
 
 pub const INTENT_MAX_LEN: usize = 512;
 pub const HASH_MAX_LEN: usize = 64;
+pub const MAX_ASSIGNED_NODES: usize = 3;
+// Two thirds, rounded down so two of three matching results reach it.
+// economy-params.json rounds the same threshold up to 67% (consensusThresholdPercentage).
+pub const CONSENSUS_THRESHOLD_BPS: u16 = 6_666;
 pub const CID_MAX_LEN: usize = 128;
 pub const REASON_MAX_LEN: usize = 256;
 pub const EVIDENCE_MAX_LEN: usize = 128;
@@ -33,6 +37,24 @@ pub struct TroTask {
     pub updated_ts: i64,
     pub last_actor: Pubkey,
     pub dispute_count: u8,
+    pub proposal: Option<Pubkey>,
+    pub stake_escrowed: bool,
+    pub result_node: Pubkey,
+    pub claimed_ts: i64,
+    pub reasoned_ts: i64,
+    pub rated: bool,
+    pub verified: bool,
+    pub commit_reveal: bool,
+    pub commit_deadline: i64,
+    pub reveal_deadline: i64,
+    pub commit_count: u8,
+    pub reveal_count: u8,
+    pub required_nodes: u8,
+    pub assigned_nodes: Vec<Pubkey>,
+    pub dissenters: Vec<Pubkey>,
+    pub round: u8,
+    // Nodes other than result_node whose revealed result matched the quorum.
+    pub agreeing_nodes: Vec<Pubkey>,
 }
 
 impl TroTask {
@@ -59,11 +81,35 @@ impl TroTask {
         8 + // created_ts
         8 + // updated_ts
         32 + // last_actor
-        1; // dispute_count
+        1 + // dispute_count
+        1 + 32 + // proposal (Option)
+        1 + // stake_escrowed
+        32 + // result_node
+        8 + // claimed_ts
+        8 + // reasoned_ts
+        1 + // rated
+        1 + // verified
+        1 + // commit_reveal
+        8 + // commit_deadline
+        8 + // reveal_deadline
+        1 + // commit_count
+        1 + // reveal_count
+        1 + // required_nodes
+        (4 + 32 * MAX_ASSIGNED_NODES) + // assigned_nodes
+        (4 + 32 * MAX_ASSIGNED_NODES) + // dissenters
+        1 + // round
+        (4 + 32 * MAX_ASSIGNED_NODES); // agreeing_nodes
+
+    /// Matching results needed before the task can move on to verification.
+    pub fn quorum(&self) -> usize {
+        let required = self.required_nodes as u32 * CONSENSUS_THRESHOLD_BPS as u32;
+        required.div_ceil(10_000) as usize
+    }
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TaskStatus {
+    #[default]
     Pending,
     Reasoning,
     Verifying,
@@ -74,52 +120,52 @@ pub enum TaskStatus {
     Cancelled,
 }
 
-impl Default for TaskStatus {
-    fn default() -> Self {
-        TaskStatus::Pending
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TaskType {
     SimpleQa,
+    #[default]
     Analytical,
     MultiStep,
     Governance,
     Clearing,
 }
 
-impl Default for TaskType {
-    fn default() -> Self {
-        TaskType::Analytical
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum TaskCriticality {
     Low,
+    #[default]
     Standard,
     High,
     MissionCritical,
 }
 
-impl Default for TaskCriticality {
-    fn default() -> Self {
-        TaskCriticality::Standard
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum WorkflowClass {
     FastRealtime,
+    #[default]
     Balanced,
     DeepReasoning,
     ConsensusGuarded,
 }
 
-impl Default for WorkflowClass {
-    fn default() -> Self {
-        WorkflowClass::Balanced
+impl WorkflowClass {
+    /// Independent nodes that must analyze a task of this workflow.
+    pub fn required_nodes(&self) -> u8 {
+        match self {
+            WorkflowClass::ConsensusGuarded => MAX_ASSIGNED_NODES as u8,
+            _ => 1,
+        }
+    }
+
+    /// Time a node has from claiming a task to submitting reasoning for the
+    /// result to count as on time.
+    pub fn reasoning_budget_secs(&self) -> i64 {
+        match self {
+            WorkflowClass::FastRealtime => 60,
+            WorkflowClass::Balanced => 300,
+            WorkflowClass::DeepReasoning => 900,
+            WorkflowClass::ConsensusGuarded => 1_800,
+        }
     }
 }
 
@@ -145,6 +191,35 @@ impl KnowledgeGraphState {
         (4 + CID_MAX_LEN); // metadata_uri (IPFS)
 }
 
+/// A node's sealed reasoning result on a commit-reveal task. The commitment is
+/// sha256(result_hash || salt || node owner); the bond is returned on reveal and
+/// forfeited otherwise.
+#[account]
+pub struct ReasoningCommit {
+    pub task_id: u64,
+    pub round: u8,
+    pub node: Pubkey,
+    pub commitment: [u8; 32],
+    pub bond: u64,
+    pub committed_at: i64,
+    pub revealed: bool,
+    pub revealed_at: i64,
+    pub result_hash: String,
+}
+
+impl ReasoningCommit {
+    pub const MAX_SIZE: usize = 8 + // discriminator
+        8 + // task_id
+        1 + // round
+        32 + // node
+        32 + // commitment
+        8 + // bond
+        8 + // committed_at
+        1 + // revealed
+        8 + // revealed_at
+        (4 + HASH_MAX_LEN); // result_hash
+}
+
 #[account]
 pub struct ChallengeRecord {
     pub task_id: u64,
@@ -171,33 +246,23 @@ impl ChallengeRecord {
         8; // resolved_at
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ChallengeStatus {
+    #[default]
     Pending,
     UnderReview,
     Resolved,
     Rejected,
 }
 
-impl Default for ChallengeStatus {
-    fn default() -> Self {
-        ChallengeStatus::Pending
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ResolutionOutcome {
+    #[default]
     Pending,
     Upheld,
     Overturned,
 }
 
-impl Default for ResolutionOutcome {
-    fn default() -> Self {
-        ResolutionOutcome::Pending
-    }
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub struct ProofPolicy {
     pub requires_zk: bool,
@@ -249,6 +314,9 @@ pub struct EconomyConfig {
     pub cycle_length_slots: u64,
     pub slash_pool: u64,
     pub last_rebalance_slot: u64,
+    pub task_timeout_secs: i64,
+    pub offline_grace_secs: i64,
+    pub offline_penalty_bps_per_hour: u16,
     pub bump: u8,
 }
 
@@ -264,6 +332,9 @@ impl EconomyConfig {
         8 + // cycle_length_slots
         8 + // slash_pool
         8 + // last_rebalance_slot
+        8 + // task_timeout_secs
+        8 + // offline_grace_secs
+        2 + // offline_penalty_bps_per_hour
         1; // bump
 }
 
@@ -283,6 +354,9 @@ impl RewardVault {
         1; // bump
 }
 
+/// Escrows a node's stake. Node instructions take it as an optional account,
+/// required whenever the node's `stake_vault_bump` is set; only nodes that never
+/// escrowed stake may leave it out.
 #[account]
 pub struct StakeVault {
     pub owner: Pubkey,
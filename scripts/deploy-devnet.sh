@@ -122,6 +122,22 @@ EOF
     cd ../..
 }
 
+# Hand the upgrade authority to the governance PDA so upgrades go through UpgradeProgram proposals
+transfer_upgrade_authority() {
+    echo -e "\n${YELLOW}Handing upgrade authority to governance...${NC}"
+    
+    PROGRAM_ID=$(cat deployments/devnet.json | grep programId | cut -d'"' -f4)
+    GOVERNANCE_AUTHORITY=$(solana find-program-derived-address "$PROGRAM_ID" string:governance-authority | awk '{print $1}')
+    
+    solana program set-upgrade-authority "$PROGRAM_ID" \
+        --new-upgrade-authority "$GOVERNANCE_AUTHORITY" \
+        --skip-new-upgrade-authority-signer-check
+    
+    echo -e "${GREEN}Upgrade authority: $GOVERNANCE_AUTHORITY${NC}"
+    echo "Upgrade buffers must use the same authority:"
+    echo "  solana program set-buffer-authority <BUFFER> --new-buffer-authority $GOVERNANCE_AUTHORITY"
+}
+
 # Build and start backend
 setup_backend() {
     echo -e "\n${YELLOW}Setting up backend...${NC}"
@@ -225,6 +241,12 @@ main() {
     configure_solana
     build_program
     deploy_program
+    
+    # Optionally hand program upgrades to governance
+    if [[ " $* " == *" --governance-upgrades "* ]]; then
+        transfer_upgrade_authority
+    fi
+    
     setup_backend
     setup_frontend
     
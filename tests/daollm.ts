@@ -1,8 +1,16 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Daollm } from "../target/types/daollm";
-import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  PublicKey,
+  Keypair,
+  SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+} from "@solana/web3.js";
+import { createMint, getOrCreateAssociatedTokenAccount, mintTo } from "@solana/spl-token";
 import { expect } from "chai";
+import { createHash } from "crypto";
 
 describe("daollm", () => {
   // Configure the client
@@ -10,13 +18,404 @@ describe("daollm", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Daollm as Program<Daollm>;
+  const wallet = provider.wallet as anchor.Wallet;
   
   const submitter = Keypair.generate();
   const nodeOwner = Keypair.generate();
+  // Holds most of the escrowed supply, so its vote alone meets quorum
+  const voter = Keypair.generate();
+  const VOTER_TOKENS = 1_000_000;
+  const VOTING_SECS = 5;
+  const TASK_TIMEOUT_SECS = 2;
   
   let proposalId: string;
   let proposalPda: PublicKey;
   let nodePda: PublicKey;
+  let inferenceResultPda: PublicKey;
+  let resultHash: string;
+  let governanceMint: PublicKey;
+  let voterTokenAccount: PublicKey;
+  let nextProposalId = Date.now();
+
+  type ProposalType = Parameters<typeof program.methods.createGovernanceProposal>[1];
+  type VoteStrategy = Parameters<typeof program.methods.createGovernanceProposal>[10];
+  type Proposal = { id: anchor.BN; address: PublicKey };
+
+  const pda = (...seeds: (string | Buffer)[]) =>
+    PublicKey.findProgramAddressSync(
+      seeds.map((seed) => (typeof seed === "string" ? Buffer.from(seed) : seed)),
+      program.programId
+    )[0];
+  const u64 = (value: anchor.BN | number) => new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+  const voterRecordOf = (owner: PublicKey) => pda("voter-record", owner.toBuffer());
+  const escrowOf = (owner: PublicKey) => pda("voter-escrow", owner.toBuffer());
+  const governanceConfig = pda("governance-config");
+  const programState = pda("program-state");
+
+  async function airdrop(to: PublicKey, sol = 2) {
+    const signature = await provider.connection.requestAirdrop(
+      to,
+      sol * anchor.web3.LAMPORTS_PER_SOL
+    );
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  }
+
+  // Voting power and delegations only count from the slot after they land
+  async function nextSlot() {
+    const slot = await provider.connection.getSlot();
+    while ((await provider.connection.getSlot()) <= slot) {
+      await sleep(100);
+    }
+  }
+
+  async function clusterTime(): Promise<number> {
+    const clock = await provider.connection.getAccountInfo(anchor.web3.SYSVAR_CLOCK_PUBKEY);
+    // Clock sysvar: slot, epoch_start_timestamp, epoch, leader_schedule_epoch, unix_timestamp
+    return new anchor.BN(clock!.data.subarray(32, 40), "le").toNumber();
+  }
+
+  async function waitPast(unixTimestamp: number) {
+    while ((await clusterTime()) <= unixTimestamp) {
+      await sleep(500);
+    }
+  }
+
+  async function expectError(action: Promise<unknown>, code: string) {
+    try {
+      await action;
+    } catch (err) {
+      const actual = err instanceof anchor.AnchorError ? err.error.errorCode.code : String(err);
+      expect(actual).to.include(code);
+      return;
+    }
+    expect.fail(`expected ${code}`);
+  }
+
+  async function createVoter(owner: Keypair, amount: number) {
+    await airdrop(owner.publicKey);
+    const tokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      wallet.payer,
+      governanceMint,
+      owner.publicKey
+    );
+    await mintTo(
+      provider.connection,
+      wallet.payer,
+      governanceMint,
+      tokenAccount.address,
+      wallet.payer,
+      amount
+    );
+    await program.methods
+      .createVoterRecord()
+      .accountsPartial({
+        voter: owner.publicKey,
+        governanceConfig,
+        governanceMint,
+        voterRecord: voterRecordOf(owner.publicKey),
+        escrow: escrowOf(owner.publicKey),
+      })
+      .signers([owner])
+      .rpc();
+    await program.methods
+      .depositGovernanceTokens(new anchor.BN(amount))
+      .accountsPartial({
+        voter: owner.publicKey,
+        voterRecord: voterRecordOf(owner.publicKey),
+        escrow: escrowOf(owner.publicKey),
+        voterTokenAccount: tokenAccount.address,
+      })
+      .signers([owner])
+      .rpc();
+    await nextSlot();
+    return tokenAccount.address;
+  }
+
+  async function createProposal(
+    proposalType: ProposalType,
+    payload: {
+      targetConfig?: Parameters<typeof program.methods.createGovernanceProposal>[3];
+      rewardRateUpdate?: Parameters<typeof program.methods.createGovernanceProposal>[4];
+      nodeStakeUpdate?: Parameters<typeof program.methods.createGovernanceProposal>[5];
+      pauseUpdate?: Parameters<typeof program.methods.createGovernanceProposal>[6];
+      programUpgrade?: Parameters<typeof program.methods.createGovernanceProposal>[7];
+      treasurySpend?: Parameters<typeof program.methods.createGovernanceProposal>[8];
+    } = {},
+    voteStrategy: VoteStrategy = { linear: {} },
+    votingSecs = VOTING_SECS
+  ): Promise<Proposal> {
+    const id = new anchor.BN(nextProposalId++);
+    const address = pda("governance_proposal", voter.publicKey.toBuffer(), u64(id));
+    await program.methods
+      .createGovernanceProposal(
+        id,
+        proposalType,
+        "integration test proposal",
+        payload.targetConfig ?? null,
+        payload.rewardRateUpdate ?? null,
+        payload.nodeStakeUpdate ?? null,
+        payload.pauseUpdate ?? null,
+        payload.programUpgrade ?? null,
+        payload.treasurySpend ?? null,
+        new anchor.BN(votingSecs),
+        voteStrategy
+      )
+      .accountsPartial({
+        proposer: voter.publicKey,
+        governanceConfig,
+        governanceMint,
+        programState,
+        proposerRecord: voterRecordOf(voter.publicKey),
+        proposal: address,
+      })
+      .signers([voter])
+      .rpc();
+    return { id, address };
+  }
+
+  function finalize(proposal: Proposal) {
+    return program.methods
+      .finalizeGovernanceProposal(proposal.id)
+      .accountsPartial({
+        finalizer: wallet.publicKey,
+        proposal: proposal.address,
+        proposer: voter.publicKey,
+        treasury: pda("treasury"),
+        proposalInstructions: pda("proposal-instructions", proposal.address.toBuffer()),
+      })
+      .rpc();
+  }
+
+  async function waitForVotingEnd(proposal: Proposal) {
+    const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+    await waitPast(proposalAccount.votingEndsAt.toNumber());
+  }
+
+  function queue(proposal: Proposal) {
+    return program.methods
+      .queueProposal(proposal.id)
+      .accountsPartial({ queuer: wallet.publicKey, proposal: proposal.address })
+      .rpc();
+  }
+
+  // Votes the proposal through with the main voter and queues it behind its timelock
+  async function passAndQueue(proposal: Proposal) {
+    await vote(proposal);
+    await waitForVotingEnd(proposal);
+    await finalize(proposal);
+    await queue(proposal);
+  }
+
+  // Only the accounts a proposal type needs are passed; the rest stay null
+  function executeAccounts(proposal: Proposal) {
+    return {
+      executor: wallet.publicKey,
+      proposal: proposal.address,
+      modelConfig: null,
+      modelConfigHistory: null,
+      proposalInstructions: null,
+      proposer: null,
+      economyConfig: null,
+      programState,
+      governanceAuthority: pda("governance-authority"),
+      program: null,
+      programData: null,
+      buffer: null,
+      spill: null,
+      rent: null,
+      clock: null,
+      bpfLoaderUpgradeable: null,
+      treasury: null,
+      treasuryRecipient: null,
+      treasuryTokenAccount: null,
+      recipientTokenAccount: null,
+      tokenProgram: null,
+    };
+  }
+
+  function attachInstructions(proposal: Proposal, instructions: anchor.web3.TransactionInstruction[]) {
+    return program.methods
+      .attachProposalInstructions(
+        proposal.id,
+        instructions.map((instruction) => ({
+          programId: instruction.programId,
+          accounts: instruction.keys,
+          data: instruction.data,
+        }))
+      )
+      .accountsPartial({
+        proposer: voter.publicKey,
+        proposal: proposal.address,
+        proposalInstructions: pda("proposal-instructions", proposal.address.toBuffer()),
+      })
+      .signers([voter])
+      .rpc();
+  }
+
+  function spendLimitInstruction(maxPerPeriod: number, periodSecs = 3600) {
+    return program.methods
+      .setTreasurySpendLimit(null, new anchor.BN(maxPerPeriod), new anchor.BN(periodSecs))
+      .accountsPartial({
+        governanceAuthority: pda("governance-authority"),
+        treasury: pda("treasury"),
+      })
+      .instruction();
+  }
+
+  // Treasury limits can only be set by the governance PDA, i.e. through a Custom proposal
+  async function setSolSpendLimit(maxPerPeriod: number, periodSecs = 3600) {
+    const proposal = await createProposal({ custom: {} });
+    await attachInstructions(proposal, [await spendLimitInstruction(maxPerPeriod, periodSecs)]);
+    await passAndQueue(proposal);
+    await program.methods
+      .executeProposal(proposal.id)
+      .accountsPartial({
+        ...executeAccounts(proposal),
+        proposalInstructions: pda("proposal-instructions", proposal.address.toBuffer()),
+        proposer: voter.publicKey,
+      })
+      .remainingAccounts([
+        { pubkey: pda("treasury"), isWritable: true, isSigner: false },
+        { pubkey: program.programId, isWritable: false, isSigner: false },
+      ])
+      .rpc();
+    return proposal;
+  }
+
+  function vote(
+    proposal: Proposal,
+    caster: Keypair = voter,
+    convictionPeriods = 0,
+    delegated: anchor.web3.AccountMeta[] = []
+  ) {
+    return program.methods
+      .voteOnProposal(proposal.id, { for: {} }, convictionPeriods)
+      .accountsPartial({
+        voter: caster.publicKey,
+        proposal: proposal.address,
+        vote: pda("vote", caster.publicKey.toBuffer(), u64(proposal.id)),
+        voterRecord: voterRecordOf(caster.publicKey),
+        programState,
+        delegation: pda("delegation", caster.publicKey.toBuffer()),
+        delegateVote: null,
+      })
+      .remainingAccounts(delegated)
+      .signers([caster])
+      .rpc();
+  }
+
+  type Workflow = Parameters<typeof program.methods.submitIntentTask>[3];
+  const taskPda = (taskId: anchor.BN) => pda("tro-task", u64(taskId));
+  const nodeOf = (owner: PublicKey) => pda("reasoning-node", owner.toBuffer());
+  let nextTaskId = Date.now();
+
+  async function registerNode(owner: Keypair, stake = anchor.web3.LAMPORTS_PER_SOL / 10) {
+    await airdrop(owner.publicKey);
+    await program.methods
+      .registerReasoningNode(
+        owner.publicKey,
+        { local7B: {} },
+        { balanced: {} },
+        new anchor.BN(0),
+        new anchor.BN(stake)
+      )
+      .accountsPartial({
+        owner: owner.publicKey,
+        reasoningNode: nodeOf(owner.publicKey),
+        stakeVault: pda("stake-vault", owner.publicKey.toBuffer()),
+        programState,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  // Standard criticality keeps the requested workflow; ConsensusGuarded tasks
+  // take three nodes and seal results with commit-reveal
+  async function submitTask(workflow: Workflow) {
+    const taskId = new anchor.BN(nextTaskId++);
+    await program.methods
+      .submitIntentTask(
+        taskId,
+        "integration test intent",
+        { analytical: {} },
+        workflow,
+        { standard: {} },
+        500,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        "metadata",
+        new anchor.BN(1800)
+      )
+      .accountsPartial({ submitter: wallet.publicKey, task: taskPda(taskId), programState })
+      .rpc();
+    return taskId;
+  }
+
+  function claim(taskId: anchor.BN, owner: Keypair, proposal: PublicKey | null = null) {
+    return program.methods
+      .claimTask(taskId)
+      .accountsPartial({
+        nodeOwner: owner.publicKey,
+        reasoningNode: nodeOf(owner.publicKey),
+        task: taskPda(taskId),
+        proposal,
+        programState,
+      })
+      .signers([owner])
+      .rpc();
+  }
+
+  // Submits a DAO proposal that opens a linked Balanced governance analysis task
+  async function submitAnalyzedProposal(stakePool: number) {
+    const taskId = new anchor.BN(nextTaskId++);
+    const proposalId = `analyzed-${taskId.toString()}`;
+    const proposal = pda("proposal", proposalId);
+    await program.methods
+      .submitProposal(proposalId, "QmAnalyzedProposal", {
+        taskId,
+        workflow: { balanced: {} },
+        criticality: { standard: {} },
+        complexityScore: 500,
+        stakePool: new anchor.BN(stakePool),
+        minNodeStake: new anchor.BN(0),
+        challengeWindowSecs: new anchor.BN(1800),
+      })
+      .accountsPartial({
+        submitter: submitter.publicKey,
+        proposal,
+        analysisTask: taskPda(taskId),
+        programState,
+      })
+      .signers([submitter])
+      .rpc();
+    return { proposalId, proposal, taskId };
+  }
+
+  // Permissionless: anyone may reclaim a claim that outlived the task timeout
+  async function reclaim(taskId: anchor.BN, owner: PublicKey) {
+    const task = await program.account.troTask.fetch(taskPda(taskId));
+    return program.methods
+      .reclaimStalledTask(taskId, owner)
+      .accountsPartial({
+        caller: wallet.publicKey,
+        task: taskPda(taskId),
+        reasoningNode: nodeOf(owner),
+        commit: pda("reasoning-commit", u64(taskId), Buffer.from([task.round]), owner.toBuffer()),
+        proposal: task.proposal,
+        stakeVault: pda("stake-vault", owner.toBuffer()),
+        rewardVault: pda("reward-vault"),
+        economyConfig: pda("economy-config"),
+        programState,
+      })
+      .rpc();
+  }
+
+  async function waitForClaimTimeout(owner: PublicKey) {
+    const node = await program.account.reasoningNode.fetch(nodeOf(owner));
+    await waitPast(node.claimedTs.toNumber() + TASK_TIMEOUT_SECS);
+  }
 
   before(async () => {
     // Airdrop SOL to test accounts
@@ -31,6 +430,53 @@ describe("daollm", () => {
     
     // Wait for airdrop confirmation
     await new Promise(resolve => setTimeout(resolve, 1000));
+
+    // Node registration is gated by the program pause state
+    governanceMint = await createMint(
+      provider.connection,
+      wallet.payer,
+      wallet.publicKey,
+      null,
+      0
+    );
+    await program.methods
+      .initializeGovernance()
+      .accounts({
+        authority: wallet.publicKey,
+        governanceMint,
+      })
+      .rpc();
+    await program.methods
+      .initializeProgramState()
+      .accounts({ authority: wallet.publicKey })
+      .rpc();
+
+    // Forfeited proposal deposits are paid into the treasury on finalization
+    await program.methods
+      .initializeTreasury()
+      .accountsPartial({ authority: wallet.publicKey, governanceConfig, treasury: pda("treasury") })
+      .rpc();
+    voterTokenAccount = await createVoter(voter, VOTER_TOKENS);
+
+    // A zero stake floor and ceiling keep dynamic stake updates from binding stake
+    await program.methods
+      .initializeEconomy(
+        1_000,
+        10_000,
+        5_000,
+        new anchor.BN(0),
+        new anchor.BN(0),
+        new anchor.BN(100),
+        new anchor.BN(TASK_TIMEOUT_SECS),
+        new anchor.BN(3600),
+        10
+      )
+      .accountsPartial({
+        authority: wallet.publicKey,
+        economyConfig: pda("economy-config"),
+        rewardVault: pda("reward-vault"),
+      })
+      .rpc();
   });
 
   it("Submits a proposal", async () => {
@@ -47,10 +493,12 @@ describe("daollm", () => {
     proposalPda = proposal;
 
     const tx = await program.methods
-      .submitProposal(proposalId, ipfsHash)
+      .submitProposal(proposalId, ipfsHash, null)
       .accounts({
         submitter: submitter.publicKey,
         proposal: proposalPda,
+        analysisTask: null,
+        programState: null,
         systemProgram: SystemProgram.programId,
       })
       .signers([submitter])
@@ -65,11 +513,11 @@ describe("daollm", () => {
   });
 
   it("Registers an inference node", async () => {
-    const stakeAmount = new anchor.BN(1000 * anchor.web3.LAMPORTS_PER_SOL);
+    const stakeAmount = new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 2);
     
     const [node] = PublicKey.findProgramAddressSync(
       [
-        Buffer.from("node"),
+        Buffer.from("reasoning-node"),
         nodeOwner.publicKey.toBuffer(),
       ],
       program.programId
@@ -77,10 +525,16 @@ describe("daollm", () => {
     nodePda = node;
 
     const tx = await program.methods
-      .registerNode(stakeAmount)
+      .registerReasoningNode(
+        nodeOwner.publicKey,
+        { local7B: {} },
+        { balanced: {} },
+        new anchor.BN(0),
+        stakeAmount
+      )
       .accounts({
         owner: nodeOwner.publicKey,
-        node: nodePda,
+        reasoningNode: nodePda,
         systemProgram: SystemProgram.programId,
       })
       .signers([nodeOwner])
@@ -88,27 +542,36 @@ describe("daollm", () => {
 
     console.log("Node registration transaction:", tx);
 
-    const nodeAccount = await program.account.inferenceNode.fetch(nodePda);
+    const nodeAccount = await program.account.reasoningNode.fetch(nodePda);
     expect(nodeAccount.owner.toString()).to.equal(nodeOwner.publicKey.toString());
     expect(nodeAccount.stakeAmount.toNumber()).to.equal(stakeAmount.toNumber());
-    expect(nodeAccount.isActive).to.be.true;
+    expect(nodeAccount.status).to.have.property("active");
+
+    // The initial stake is escrowed, not just credited
+    const [stakeVault] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake-vault"), nodeOwner.publicKey.toBuffer()],
+      program.programId
+    );
+    const vaultAccount = await program.account.stakeVault.fetch(stakeVault);
+    expect(vaultAccount.totalStake.toNumber()).to.equal(stakeAmount.toNumber());
   });
 
   it("Submits an inference result", async () => {
-    const resultHash = "QmResultHash987654321";
-    const confidence = 85;
+    resultHash = "QmResultHash987654321";
+    const confidenceBps = 8500;
 
     const [inferenceResult] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("inference"),
         proposalPda.toBuffer(),
-        nodePda.toBuffer(),
+        nodeOwner.publicKey.toBuffer(),
       ],
       program.programId
     );
+    inferenceResultPda = inferenceResult;
 
     const tx = await program.methods
-      .submitInference(proposalId, resultHash, confidence)
+      .submitInference(proposalId, resultHash, confidenceBps)
       .accounts({
         node: nodeOwner.publicKey,
         nodeAccount: nodePda,
@@ -125,7 +588,7 @@ describe("daollm", () => {
     const inferenceAccount = await program.account.inferenceResult.fetch(inferenceResult);
     expect(inferenceAccount.proposalId).to.equal(proposalId);
     expect(inferenceAccount.resultHash).to.equal(resultHash);
-    expect(inferenceAccount.confidence).to.equal(confidence);
+    expect(inferenceAccount.confidenceBps).to.equal(confidenceBps);
   });
 
   it("Aggregates inference results", async () => {
@@ -136,12 +599,20 @@ describe("daollm", () => {
     );
     await new Promise(resolve => setTimeout(resolve, 1000));
 
+    // Single contributor: the Merkle root is the sha256 leaf of its result PDA
+    const contributorsRoot = Array.from(
+      createHash("sha256").update(inferenceResultPda.toBuffer()).digest()
+    );
+
     const tx = await program.methods
-      .aggregateResults(proposalId)
+      .aggregateResults(proposalId, resultHash, contributorsRoot, 10000)
       .accounts({
         aggregator: aggregator.publicKey,
         proposal: proposalPda,
       })
+      .remainingAccounts([
+        { pubkey: inferenceResultPda, isWritable: false, isSigner: false },
+      ])
       .signers([aggregator])
       .rpc();
 
@@ -150,9 +621,11 @@ describe("daollm", () => {
     const proposalAccount = await program.account.proposal.fetch(proposalPda);
     // Check that status is Completed (enum value 2)
     expect(proposalAccount.status).to.have.property("completed");
+    expect(proposalAccount.consensusHash).to.equal(resultHash);
+    expect(proposalAccount.agreementBps).to.equal(10000);
   });
 
-  it("Rates a node", async () => {
+  it("Rejects ratings from accounts without a completed task", async () => {
     const rater = Keypair.generate();
     await provider.connection.requestAirdrop(
       rater.publicKey,
@@ -160,23 +633,1520 @@ describe("daollm", () => {
     );
     await new Promise(resolve => setTimeout(resolve, 1000));
 
-    const score = 90;
+    const taskId = new anchor.BN(Date.now());
+    const [task] = PublicKey.findProgramAddressSync(
+      [Buffer.from("tro-task"), taskId.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+    let rejected = false;
+    try {
+      await program.methods
+        .rateNode(nodeOwner.publicKey, taskId, 90)
+        .accounts({
+          rater: rater.publicKey,
+          node: nodePda,
+          task,
+        })
+        .signers([rater])
+        .rpc();
+    } catch (err) {
+      rejected = true;
+    }
+    expect(rejected).to.be.true;
 
-    const tx = await program.methods
-      .rateNode(nodeOwner.publicKey, score)
-      .accounts({
-        rater: rater.publicKey,
-        node: nodePda,
-        nodeAddress: nodeOwner.publicKey,
-      })
-      .signers([rater])
+    // Reputation only moves through verified outcomes and task submitter ratings
+    const nodeAccount = await program.account.reasoningNode.fetch(nodePda);
+    expect(nodeAccount.reputationScoreBps).to.equal(6000);
+  });
+
+  describe("consensus aggregation", () => {
+    const nodes = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const hashes = ["QmAgreedResult", "QmAgreedResult", "QmDissentingResult"];
+    const aggregatedId = `aggregated-${Date.now()}`;
+    const aggregated = pda("proposal", aggregatedId);
+    const results = nodes.map((node) =>
+      pda("inference", aggregated.toBuffer(), node.publicKey.toBuffer())
+    );
+    const sha256 = (...parts: Buffer[]) =>
+      createHash("sha256").update(Buffer.concat(parts)).digest();
+    // Leaves are the sha256 of each agreeing result PDA, sorted before pairing
+    const leaves = results.map((result) => sha256(result.toBuffer()));
+    const [left, right] = leaves.slice(0, 2).sort(Buffer.compare);
+    const agreedRoot = Array.from(sha256(left, right));
+
+    function aggregate(
+      consensusHash: string,
+      root: number[],
+      agreementBps: number,
+      accounts = results
+    ) {
+      return program.methods
+        .aggregateResults(aggregatedId, consensusHash, root, agreementBps)
+        .accounts({ aggregator: wallet.publicKey, proposal: aggregated })
+        .remainingAccounts(
+          accounts.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false }))
+        )
+        .rpc();
+    }
+
+    before(async () => {
+      await program.methods
+        .submitProposal(aggregatedId, "QmAggregatedProposal", null)
+        .accounts({
+          submitter: submitter.publicKey,
+          proposal: aggregated,
+          analysisTask: null,
+          programState: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([submitter])
+        .rpc();
+      for (const [index, node] of nodes.entries()) {
+        await registerNode(node);
+        await program.methods
+          .submitInference(aggregatedId, hashes[index], 9000)
+          .accountsPartial({
+            node: node.publicKey,
+            nodeAccount: nodeOf(node.publicKey),
+            proposal: aggregated,
+            inferenceResult: results[index],
+          })
+          .signers([node])
+          .rpc();
+      }
+    });
+
+    it("Requires every submitted result", async () => {
+      await expectError(
+        aggregate("QmAgreedResult", agreedRoot, 6_666, results.slice(0, 2)),
+        "InvalidConsensus"
+      );
+    });
+
+    it("Rejects an agreement ratio the results do not support", async () => {
+      await expectError(aggregate("QmAgreedResult", agreedRoot, 10_000), "InvalidConsensus");
+    });
+
+    it("Rejects a contributors root that leaves out an agreeing node", async () => {
+      await expectError(
+        aggregate("QmAgreedResult", Array.from(leaves[0]), 6_666),
+        "InvalidConsensus"
+      );
+    });
+
+    it("Rejects a consensus hash backed by less than two thirds", async () => {
+      await expectError(
+        aggregate("QmDissentingResult", Array.from(leaves[2]), 3_333),
+        "ConsensusNotReached"
+      );
+    });
+
+    it("Completes the proposal once two thirds of the results agree", async () => {
+      await aggregate("QmAgreedResult", agreedRoot, 6_666);
+
+      const proposalAccount = await program.account.proposal.fetch(aggregated);
+      expect(proposalAccount.status).to.have.property("completed");
+      expect(proposalAccount.agreementBps).to.equal(6_666);
+      expect(proposalAccount.contributorsRoot).to.deep.equal(agreedRoot);
+    });
+  });
+
+  describe("escrowed voting", () => {
+    let proposal: Proposal;
+
+    before(async () => {
+      proposal = await createProposal({ custom: {} });
+    });
+
+    it("Counts escrowed tokens as voting power", async () => {
+      await vote(proposal);
+
+      const voteAccount = await program.account.vote.fetch(
+        pda("vote", voter.publicKey.toBuffer(), u64(proposal.id))
+      );
+      expect(voteAccount.votingPower.toNumber()).to.equal(VOTER_TOKENS);
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(VOTER_TOKENS);
+    });
+
+    it("Keeps escrowed tokens locked until voting ends", async () => {
+      await expectError(
+        program.methods
+          .withdrawGovernanceTokens(new anchor.BN(1))
+          .accountsPartial({
+            voter: voter.publicKey,
+            voterRecord: voterRecordOf(voter.publicKey),
+            escrow: escrowOf(voter.publicKey),
+            voterTokenAccount,
+          })
+          .signers([voter])
+          .rpc(),
+        "TokensLocked"
+      );
+    });
+  });
+
+  describe("changing and withdrawing votes", () => {
+    const voteOf = (proposal: Proposal) => pda("vote", voter.publicKey.toBuffer(), u64(proposal.id));
+
+    function changeVote(proposal: Proposal) {
+      return program.methods
+        .changeVote(proposal.id, { against: {} })
+        .accountsPartial({
+          voter: voter.publicKey,
+          proposal: proposal.address,
+          vote: voteOf(proposal),
+          programState,
+        })
+        .signers([voter])
+        .rpc();
+    }
+
+    function withdrawVote(proposal: Proposal) {
+      return program.methods
+        .withdrawVote(proposal.id)
+        .accountsPartial({
+          voter: voter.publicKey,
+          proposal: proposal.address,
+          vote: voteOf(proposal),
+          programState,
+        })
+        .signers([voter])
+        .rpc();
+    }
+
+    it("Moves the voter's power to the new choice", async () => {
+      const proposal = await createProposal({ custom: {} }, {}, { linear: {} }, 3600);
+      await vote(proposal);
+      await changeVote(proposal);
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(0);
+      expect(proposalAccount.votesAgainst.toNumber()).to.equal(VOTER_TOKENS);
+      expect(proposalAccount.totalVotes.toNumber()).to.equal(VOTER_TOKENS);
+    });
+
+    it("Removes a withdrawn vote from the tally and closes its account", async () => {
+      const proposal = await createProposal({ custom: {} }, {}, { linear: {} }, 3600);
+      await vote(proposal);
+      await withdrawVote(proposal);
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(0);
+      expect(proposalAccount.totalVotes.toNumber()).to.equal(0);
+      expect(await program.account.vote.fetchNullable(voteOf(proposal))).to.be.null;
+    });
+
+    it("Rejects changes and withdrawals after voting ends", async () => {
+      const proposal = await createProposal({ custom: {} });
+      await vote(proposal);
+      await waitForVotingEnd(proposal);
+
+      await expectError(changeVote(proposal), "VotingEnded");
+      await expectError(withdrawVote(proposal), "VotingEnded");
+    });
+  });
+
+  describe("proposal finalization", () => {
+    it("Refuses to finalize while voting is open", async () => {
+      const proposal = await createProposal({ custom: {} }, {}, { linear: {} }, 3600);
+      await vote(proposal);
+
+      await expectError(finalize(proposal), "VotingStillActive");
+    });
+
+    it("Passes proposals that meet quorum and rejects those that do not", async () => {
+      const supported = await createProposal({ custom: {} });
+      const ignored = await createProposal({ custom: {} });
+      await attachInstructions(supported, [await spendLimitInstruction(1)]);
+      await vote(supported);
+      await waitForVotingEnd(ignored);
+
+      await finalize(supported);
+      await finalize(ignored);
+
+      const supportedAccount = await program.account.governanceProposal.fetch(supported.address);
+      expect(supportedAccount.status).to.have.property("passed");
+      const ignoredAccount = await program.account.governanceProposal.fetch(ignored.address);
+      expect(ignoredAccount.status).to.have.property("rejected");
+    });
+
+    it("Rejects Custom proposals that never had instructions attached", async () => {
+      const proposal = await createProposal({ custom: {} });
+      await vote(proposal);
+      await waitForVotingEnd(proposal);
+
+      await finalize(proposal);
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.status).to.have.property("rejected");
+    });
+  });
+
+  describe("proposal deposits", () => {
+    const DEPOSIT = anchor.web3.LAMPORTS_PER_SOL / 100;
+    const balanceOf = (account: PublicKey) => provider.connection.getBalance(account);
+
+    function setProposalDeposit(deposit: number, minProposerBalance: number) {
+      return program.methods
+        .setProposalDeposit(new anchor.BN(deposit), new anchor.BN(minProposerBalance))
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+        .rpc();
+    }
+
+    function cancel(proposal: Proposal) {
+      return program.methods
+        .cancelGovernanceProposal(proposal.id)
+        .accountsPartial({ proposer: voter.publicKey, proposal: proposal.address })
+        .signers([voter])
+        .rpc();
+    }
+
+    before(async () => {
+      await setProposalDeposit(DEPOSIT, VOTER_TOKENS);
+    });
+
+    after(async () => {
+      await setProposalDeposit(0, 0);
+    });
+
+    it("Rejects proposers below the minimum escrowed balance", async () => {
+      await setProposalDeposit(DEPOSIT, VOTER_TOKENS + 1);
+      await expectError(createProposal({ custom: {} }), "InsufficientProposerBalance");
+      await setProposalDeposit(DEPOSIT, VOTER_TOKENS);
+    });
+
+    it("Refunds the deposit when the proposer cancels before any vote", async () => {
+      const proposal = await createProposal({ custom: {} });
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.depositAmount.toNumber()).to.equal(DEPOSIT);
+
+      const before = await balanceOf(voter.publicKey);
+      await cancel(proposal);
+
+      expect((await balanceOf(voter.publicKey)) - before).to.equal(DEPOSIT);
+      const cancelled = await program.account.governanceProposal.fetch(proposal.address);
+      expect(cancelled.status).to.have.property("cancelled");
+      expect(cancelled.depositAmount.toNumber()).to.equal(0);
+    });
+
+    it("Refuses cancellation once voting has started", async () => {
+      const proposal = await createProposal({ custom: {} }, {}, { linear: {} }, 3600);
+      await vote(proposal);
+
+      await expectError(cancel(proposal), "ProposalHasVotes");
+    });
+
+    it("Forfeits the deposit to the treasury when quorum is missed", async () => {
+      const proposal = await createProposal({ custom: {} });
+      await waitForVotingEnd(proposal);
+
+      const before = await balanceOf(pda("treasury"));
+      await finalize(proposal);
+
+      expect((await balanceOf(pda("treasury"))) - before).to.equal(DEPOSIT);
+    });
+
+    it("Refunds the deposit on quorum without needing the treasury", async () => {
+      const proposal = await createProposal({ custom: {} });
+      await vote(proposal);
+      await waitForVotingEnd(proposal);
+
+      const before = await balanceOf(voter.publicKey);
+      await program.methods
+        .finalizeGovernanceProposal(proposal.id)
+        .accountsPartial({
+          finalizer: wallet.publicKey,
+          proposal: proposal.address,
+          proposer: voter.publicKey,
+          treasury: null,
+          proposalInstructions: pda("proposal-instructions", proposal.address.toBuffer()),
+        })
+        .rpc();
+
+      expect((await balanceOf(voter.publicKey)) - before).to.equal(DEPOSIT);
+    });
+  });
+
+  describe("timelock and veto", () => {
+    const outsider = Keypair.generate();
+    let proposal: Proposal;
+
+    before(async () => {
+      await program.methods
+        .setGuardians([wallet.publicKey], 1)
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+        .rpc();
+
+      // Reward rate changes keep the default two-day timelock
+      proposal = await createProposal(
+        { updateRewardRate: {} },
+        {
+          rewardRateUpdate: {
+            baseRewardRateBps: 1_000,
+            highPerfMultiplierBps: 10_000,
+            lowPerfPenaltyBps: 5_000,
+          },
+        }
+      );
+      await passAndQueue(proposal);
+    });
+
+    it("Queues passed proposals behind their timelock", async () => {
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.status).to.have.property("queued");
+      expect(proposalAccount.eta.toNumber()).to.be.greaterThan(await clusterTime());
+
+      await expectError(
+        program.methods
+          .executeProposal(proposal.id)
+          .accountsPartial(executeAccounts(proposal))
+          .rpc(),
+        "TimelockNotExpired"
+      );
+    });
+
+    it("Rejects vetoes from accounts outside the guardian set", async () => {
+      await expectError(
+        program.methods
+          .vetoProposal(proposal.id)
+          .accountsPartial({
+            guardian: outsider.publicKey,
+            governanceConfig,
+            proposal: proposal.address,
+          })
+          .signers([outsider])
+          .rpc(),
+        "InsufficientGuardianApprovals"
+      );
+    });
+
+    it("Lets guardians veto a queued proposal", async () => {
+      await program.methods
+        .vetoProposal(proposal.id)
+        .accountsPartial({
+          guardian: wallet.publicKey,
+          governanceConfig,
+          proposal: proposal.address,
+        })
+        .rpc();
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.status).to.have.property("vetoed");
+    });
+  });
+
+  describe("emergency pause", () => {
+    const PAUSE_TASKS = 1 << 0;
+    const PAUSE_REWARDS = 1 << 2;
+
+    function guardianPause(pausedGroups: number) {
+      return program.methods
+        .guardianPause(false, pausedGroups)
+        .accountsPartial({ guardian: wallet.publicKey, governanceConfig, programState })
+        .rpc();
+    }
+
+    before(async () => {
+      await program.methods
+        .setProposalTimelock({ emergencyPause: {} }, new anchor.BN(0))
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+        .rpc();
+    });
+
+    it("Blocks task, reward and training instructions paused by a guardian", async () => {
+      await guardianPause(PAUSE_TASKS | PAUSE_REWARDS);
+
+      await expectError(submitTask({ balanced: {} }), "ProgramPaused");
+      const trainingTaskId = new anchor.BN(Date.now());
+      await expectError(
+        program.methods
+          .createTrainingTask(trainingTaskId, "model-config-hash", 1)
+          .accountsPartial({
+            creator: wallet.publicKey,
+            task: pda("training_task", wallet.publicKey.toBuffer(), u64(trainingTaskId)),
+            programState,
+          })
+          .rpc(),
+        "ProgramPaused"
+      );
+      await expectError(
+        program.methods
+          .claimReward({ inference: {} }, new anchor.BN(1))
+          .accountsPartial({ claimer: wallet.publicKey, rewardAccount: pda("treasury"), programState })
+          .rpc(),
+        "ProgramPaused"
+      );
+    });
+
+    it("Lets guardians add paused groups but not lift them", async () => {
+      await guardianPause(0);
+
+      const state = await program.account.programState.fetch(programState);
+      expect(state.pausedGroups).to.equal(PAUSE_TASKS | PAUSE_REWARDS);
+    });
+
+    it("Lifts the pause through an EmergencyPause proposal", async () => {
+      const proposal = await createProposal(
+        { emergencyPause: {} },
+        { pauseUpdate: { paused: false, pausedGroups: 0 } }
+      );
+      await passAndQueue(proposal);
+      await program.methods
+        .executeProposal(proposal.id)
+        .accountsPartial(executeAccounts(proposal))
+        .rpc();
+
+      const state = await program.account.programState.fetch(programState);
+      expect(state.paused).to.be.false;
+      expect(state.pausedGroups).to.equal(0);
+      await submitTask({ balanced: {} });
+    });
+  });
+
+  describe("vote delegation", () => {
+    const delegator = Keypair.generate();
+    const DELEGATOR_TOKENS = 10_000;
+    const delegation = () => pda("delegation", delegator.publicKey.toBuffer());
+
+    before(async () => {
+      await createVoter(delegator, DELEGATOR_TOKENS);
+    });
+
+    it("Rejects delegating to oneself", async () => {
+      await expectError(
+        program.methods
+          .delegateVotes(delegator.publicKey, null, null)
+          .accountsPartial({ delegator: delegator.publicKey, delegation: delegation() })
+          .signers([delegator])
+          .rpc(),
+        "InvalidDelegation"
+      );
+    });
+
+    it("Casts delegated power along with the delegate's own vote", async () => {
+      await program.methods
+        .delegateVotes(voter.publicKey, null, null)
+        .accountsPartial({ delegator: delegator.publicKey, delegation: delegation() })
+        .signers([delegator])
+        .rpc();
+      // Delegations only apply to proposals snapshotted after they were created
+      await nextSlot();
+      const proposal = await createProposal({ custom: {} });
+
+      await vote(proposal, voter, 0, [
+        { pubkey: delegation(), isWritable: true, isSigner: false },
+        { pubkey: voterRecordOf(delegator.publicKey), isWritable: true, isSigner: false },
+        {
+          pubkey: pda("vote", delegator.publicKey.toBuffer(), u64(proposal.id)),
+          isWritable: false,
+          isSigner: false,
+        },
+      ]);
+
+      const voteAccount = await program.account.vote.fetch(
+        pda("vote", voter.publicKey.toBuffer(), u64(proposal.id))
+      );
+      expect(voteAccount.delegatedPower.toNumber()).to.equal(DELEGATOR_TOKENS);
+      expect(voteAccount.votingPower.toNumber()).to.equal(VOTER_TOKENS + DELEGATOR_TOKENS);
+    });
+  });
+
+  describe("vote strategies", () => {
+    it("Weighs quadratic votes by the square root of voting power", async () => {
+      const proposal = await createProposal({ custom: {} }, {}, { quadratic: {} });
+      await vote(proposal);
+
+      const voteAccount = await program.account.vote.fetch(
+        pda("vote", voter.publicKey.toBuffer(), u64(proposal.id))
+      );
+      expect(voteAccount.votingPower.toNumber()).to.equal(VOTER_TOKENS);
+      expect(voteAccount.weight.toNumber()).to.equal(Math.sqrt(VOTER_TOKENS));
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      // Quorum still counts raw token power
+      expect(proposalAccount.totalVotes.toNumber()).to.equal(VOTER_TOKENS);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(Math.sqrt(VOTER_TOKENS));
+    });
+
+    it("Rejects conviction lock periods outside conviction voting", async () => {
+      const proposal = await createProposal({ custom: {} });
+
+      await expectError(vote(proposal, voter, 2), "InvalidConviction");
+    });
+  });
+
+  describe("proposal instructions", () => {
+    before(async () => {
+      await program.methods
+        .setProposalTimelock({ custom: {} }, new anchor.BN(0))
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+        .rpc();
+    });
+
+    it("Rejects instructions that need a signer other than the governance PDA", async () => {
+      const proposal = await createProposal({ custom: {} });
+      const transfer = SystemProgram.transfer({
+        fromPubkey: wallet.publicKey,
+        toPubkey: voter.publicKey,
+        lamports: 1,
+      });
+
+      await expectError(attachInstructions(proposal, [transfer]), "InvalidProposalInstruction");
+    });
+
+    it("Rejects instructions addressed to the upgrade loader", async () => {
+      const proposal = await createProposal({ custom: {} });
+      const loader = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+      const [programData] = PublicKey.findProgramAddressSync([program.programId.toBuffer()], loader);
+      const setAuthority = new anchor.web3.TransactionInstruction({
+        programId: loader,
+        keys: [
+          { pubkey: programData, isWritable: true, isSigner: false },
+          { pubkey: pda("governance-authority"), isWritable: false, isSigner: true },
+          { pubkey: wallet.publicKey, isWritable: false, isSigner: false },
+        ],
+        data: Buffer.from([4, 0, 0, 0]), // UpgradeableLoaderInstruction::SetAuthority
+      });
+
+      await expectError(attachInstructions(proposal, [setAuthority]), "InvalidProposalInstruction");
+    });
+
+    it("Executes attached instructions via CPI once the proposal passes", async () => {
+      const proposal = await setSolSpendLimit(5_000_000);
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.status).to.have.property("executed");
+      const treasury = await program.account.treasury.fetch(pda("treasury"));
+      const solLimit = treasury.spendLimits.find((limit) => limit.mint === null);
+      expect(solLimit!.maxPerPeriod.toNumber()).to.equal(5_000_000);
+      const instructions = pda("proposal-instructions", proposal.address.toBuffer());
+      expect(await provider.connection.getAccountInfo(instructions)).to.be.null;
+    });
+  });
+
+  describe("economy parameter proposals", () => {
+    // Keeps the stake range and task timeout the rest of the suite relies on
+    const nodeStakeUpdate = {
+      stakeFloor: new anchor.BN(0),
+      stakeCeiling: new anchor.BN(0),
+      taskTimeoutSecs: new anchor.BN(TASK_TIMEOUT_SECS),
+      offlineGraceSecs: new anchor.BN(7200),
+      offlinePenaltyBpsPerHour: 20,
+    };
+
+    function executeEconomyUpdate(proposal: Proposal) {
+      return program.methods
+        .executeProposal(proposal.id)
+        .accountsPartial({ ...executeAccounts(proposal), economyConfig: pda("economy-config") })
+        .rpc();
+    }
+
+    before(async () => {
+      for (const proposalType of [{ updateRewardRate: {} }, { updateNodeStake: {} }]) {
+        await program.methods
+          .setProposalTimelock(proposalType, new anchor.BN(0))
+          .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+          .rpc();
+      }
+    });
+
+    it("Rejects payloads that initialize_economy would reject", async () => {
+      await expectError(
+        createProposal(
+          { updateRewardRate: {} },
+          {
+            rewardRateUpdate: {
+              baseRewardRateBps: 10_001,
+              highPerfMultiplierBps: 10_000,
+              lowPerfPenaltyBps: 5_000,
+            },
+          }
+        ),
+        "InvalidScore"
+      );
+      await expectError(
+        createProposal(
+          { updateNodeStake: {} },
+          {
+            nodeStakeUpdate: {
+              ...nodeStakeUpdate,
+              stakeFloor: new anchor.BN(2),
+              stakeCeiling: new anchor.BN(1),
+            },
+          }
+        ),
+        "InvalidStakeRange"
+      );
+      await expectError(createProposal({ updateNodeStake: {} }), "InvalidProposalPayload");
+    });
+
+    it("Writes passed reward rate updates into the economy config", async () => {
+      const proposal = await createProposal(
+        { updateRewardRate: {} },
+        {
+          rewardRateUpdate: {
+            baseRewardRateBps: 2_000,
+            highPerfMultiplierBps: 9_000,
+            lowPerfPenaltyBps: 4_000,
+          },
+        }
+      );
+      await passAndQueue(proposal);
+      await executeEconomyUpdate(proposal);
+
+      const economy = await program.account.economyConfig.fetch(pda("economy-config"));
+      expect(economy.baseRewardRateBps).to.equal(2_000);
+      expect(economy.highPerfMultiplierBps).to.equal(9_000);
+      expect(economy.lowPerfPenaltyBps).to.equal(4_000);
+    });
+
+    it("Writes passed node stake updates into the economy config", async () => {
+      const proposal = await createProposal({ updateNodeStake: {} }, { nodeStakeUpdate });
+      await passAndQueue(proposal);
+      await executeEconomyUpdate(proposal);
+
+      const economy = await program.account.economyConfig.fetch(pda("economy-config"));
+      expect(economy.stakeCeiling.toNumber()).to.equal(0);
+      expect(economy.taskTimeoutSecs.toNumber()).to.equal(TASK_TIMEOUT_SECS);
+      expect(economy.offlineGraceSecs.toNumber()).to.equal(7200);
+      expect(economy.offlinePenaltyBpsPerHour).to.equal(20);
+    });
+  });
+
+  // Upgrades and model config changes keep at least a day between passing and execution
+  const MIN_CRITICAL_TIMELOCK_SECS = 24 * 3600;
+
+  function setTimelock(proposalType: ProposalType, delaySecs: number) {
+    return program.methods
+      .setProposalTimelock(proposalType, new anchor.BN(delaySecs))
+      .accountsPartial({ authority: wallet.publicKey, governanceConfig })
       .rpc();
+  }
+
+  describe("model config", () => {
+    const modelConfig = pda("model-config");
+    const modelConfigHistory = pda("model-config-history");
+    const configVersion = (version: number) => ({
+      modelVersion: new anchor.BN(version),
+      learningRate: 0.001,
+      batchSize: 32,
+      maxEpochs: 10,
+      inferenceTimeout: new anchor.BN(30),
+      minNodeReputation: 50,
+      updatedAt: new anchor.BN(0),
+      updatedBy: PublicKey.default,
+    });
 
-    console.log("Node rating transaction:", tx);
+    before(async () => {
+      await program.methods
+        .initializeModelConfig(configVersion(1))
+        .accountsPartial({
+          authority: wallet.publicKey,
+          governanceConfig,
+          modelConfig,
+          modelConfigHistory,
+        })
+        .rpc();
+    });
 
-    const nodeAccount = await program.account.inferenceNode.fetch(nodePda);
-    // Initial score is 50, new score should be (50 + 90) / 2 = 70
-    expect(nodeAccount.reputationScore).to.be.greaterThan(50);
+    it("Starts the history with the initial configuration", async () => {
+      const history = await program.account.modelConfigHistory.fetch(modelConfigHistory);
+      expect(history.entries).to.have.length(1);
+      expect(history.entries[0].config.modelVersion.toNumber()).to.equal(1);
+      expect(history.entries[0].proposal.equals(PublicKey.default)).to.be.true;
+    });
+
+    it("Refuses to shorten the model config timelock below a day", async () => {
+      await expectError(
+        setTimelock({ updateModelConfig: {} }, MIN_CRITICAL_TIMELOCK_SECS - 1),
+        "InvalidTimelock"
+      );
+    });
+
+    it("Holds passed configs behind the minimum timelock", async () => {
+      await setTimelock({ updateModelConfig: {} }, MIN_CRITICAL_TIMELOCK_SECS);
+      const proposal = await createProposal(
+        { updateModelConfig: {} },
+        { targetConfig: configVersion(2) }
+      );
+      await passAndQueue(proposal);
+
+      await expectError(
+        program.methods
+          .executeProposal(proposal.id)
+          .accountsPartial({ ...executeAccounts(proposal), modelConfig, modelConfigHistory })
+          .rpc(),
+        "TimelockNotExpired"
+      );
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.eta.toNumber()).to.be.at.least(
+        (await clusterTime()) + MIN_CRITICAL_TIMELOCK_SECS - 60
+      );
+      const config = await program.account.modelConfig.fetch(modelConfig);
+      expect(config.modelVersion.toNumber()).to.equal(1);
+    });
+  });
+
+  describe("program upgrades", () => {
+    it("Refuses to drop the upgrade timelock below a day", async () => {
+      await expectError(setTimelock({ upgradeProgram: {} }, 0), "InvalidTimelock");
+    });
+
+    let proposal: Proposal;
+
+    before(async () => {
+      proposal = await createProposal(
+        { upgradeProgram: {} },
+        {
+          programUpgrade: {
+            buffer: Keypair.generate().publicKey,
+            bufferHash: Array(32).fill(0),
+            spill: pda("treasury"),
+          },
+        }
+      );
+      await passAndQueue(proposal);
+    });
+
+    it("Keeps passed upgrades queued behind the minimum timelock", async () => {
+      await expectError(
+        program.methods.executeProposal(proposal.id).accountsPartial(executeAccounts(proposal)).rpc(),
+        "TimelockNotExpired"
+      );
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.status).to.have.property("queued");
+    });
+
+    it("Rejects spill accounts other than the one voted on", async () => {
+      const stranger = Keypair.generate();
+      await airdrop(stranger.publicKey);
+
+      await expectError(
+        program.methods
+          .executeProposal(proposal.id)
+          .accountsPartial({
+            ...executeAccounts(proposal),
+            executor: stranger.publicKey,
+            spill: stranger.publicKey,
+          })
+          .signers([stranger])
+          .rpc(),
+        "InvalidProgramUpgrade"
+      );
+    });
+  });
+
+  describe("council", () => {
+    const member = Keypair.generate();
+    const outsider = Keypair.generate();
+    const council = pda("council");
+    const councilAction = (actionId: anchor.BN) => pda("council-action", u64(actionId));
+
+    async function proposeDynamicStakeUpdate() {
+      const actionId = new anchor.BN(Date.now());
+      await program.methods
+        .proposeCouncilAction(actionId, { updateDynamicStake: { nodeOwner: nodeOwner.publicKey } })
+        .accountsPartial({
+          member: wallet.publicKey,
+          council,
+          councilAction: councilAction(actionId),
+        })
+        .rpc();
+      return actionId;
+    }
+
+    function updateDynamicStake(actionId: anchor.BN) {
+      return program.methods
+        .updateDynamicStake()
+        .accountsPartial({
+          daoAuthority: wallet.publicKey,
+          economyConfig: pda("economy-config"),
+          council,
+          councilAction: councilAction(actionId),
+          nodeOwner: nodeOwner.publicKey,
+          reasoningNode: nodePda,
+          programState,
+        })
+        .rpc();
+    }
+
+    function approve(actionId: anchor.BN, approver: Keypair) {
+      return program.methods
+        .approveCouncilAction(actionId)
+        .accountsPartial({
+          member: approver.publicKey,
+          council,
+          councilAction: councilAction(actionId),
+        })
+        .signers([approver])
+        .rpc();
+    }
+
+    before(async () => {
+      await program.methods
+        .initializeCouncil([wallet.publicKey, member.publicKey], 2)
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig, council })
+        .rpc();
+    });
+
+    it("Holds council actions until a quorum of members approves", async () => {
+      const actionId = await proposeDynamicStakeUpdate();
+
+      await expectError(updateDynamicStake(actionId), "InsufficientCouncilApprovals");
+      await expectError(approve(actionId, outsider), "NotCouncilMember");
+    });
+
+    it("Executes council actions approved by a quorum", async () => {
+      const actionId = await proposeDynamicStakeUpdate();
+      await approve(actionId, member);
+      await updateDynamicStake(actionId);
+
+      const action = await program.account.councilAction.fetch(councilAction(actionId));
+      expect(action.executedAt.toNumber()).to.be.greaterThan(0);
+      expect(action.approvals).to.have.length(2);
+    });
+  });
+
+  describe("signed votes", () => {
+    const signer = Keypair.generate();
+    const SIGNER_TOKENS = 25_000;
+    let proposal: Proposal;
+
+    // The signer signs the domain tag followed by borsh(SignedVoteMessage)
+    function relaySignedVote(votingPower: number) {
+      const message = Buffer.concat([
+        Buffer.from("daollm-signed-vote"),
+        proposal.address.toBuffer(),
+        u64(proposal.id),
+        Buffer.from([0]), // GovernanceVoteType::For
+        u64(votingPower),
+      ]);
+      return program.methods
+        .submitSignedVotes(proposal.id)
+        .accountsPartial({
+          relayer: wallet.publicKey,
+          proposal: proposal.address,
+          programState,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .remainingAccounts([
+          { pubkey: voterRecordOf(signer.publicKey), isWritable: true, isSigner: false },
+          {
+            pubkey: pda("vote", signer.publicKey.toBuffer(), u64(proposal.id)),
+            isWritable: false,
+            isSigner: false,
+          },
+          { pubkey: pda("delegation", signer.publicKey.toBuffer()), isWritable: false, isSigner: false },
+          // Placeholder: the signer has no delegation, so no delegate vote is read
+          { pubkey: SystemProgram.programId, isWritable: false, isSigner: false },
+        ])
+        .preInstructions([
+          Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message,
+          }),
+        ])
+        .rpc();
+    }
+
+    before(async () => {
+      await createVoter(signer, SIGNER_TOKENS);
+      proposal = await createProposal({ custom: {} }, {}, { linear: {} }, 3600);
+    });
+
+    it("Rejects signed votes that overstate the snapshot power", async () => {
+      await expectError(relaySignedVote(SIGNER_TOKENS + 1), "SignedVotePowerMismatch");
+    });
+
+    it("Tallies relayed signed votes", async () => {
+      await relaySignedVote(SIGNER_TOKENS);
+
+      const proposalAccount = await program.account.governanceProposal.fetch(proposal.address);
+      expect(proposalAccount.votesFor.toNumber()).to.equal(SIGNER_TOKENS);
+      const record = await program.account.voterRecord.fetch(voterRecordOf(signer.publicKey));
+      expect(record.lockedUntil.toNumber()).to.equal(proposalAccount.votingEndsAt.toNumber());
+    });
+  });
+
+  describe("treasury spending", () => {
+    const recipient = Keypair.generate();
+    const SPEND_LIMIT = 1_000_000;
+    const SPEND_AMOUNT = 600_000;
+    let first: Proposal;
+    let second: Proposal;
+
+    function executeSpend(proposal: Proposal) {
+      return program.methods
+        .executeProposal(proposal.id)
+        .accountsPartial({
+          ...executeAccounts(proposal),
+          treasury: pda("treasury"),
+          treasuryRecipient: recipient.publicKey,
+        })
+        .rpc();
+    }
+
+    before(async () => {
+      await program.methods
+        .setProposalTimelock({ treasurySpend: {} }, new anchor.BN(0))
+        .accountsPartial({ authority: wallet.publicKey, governanceConfig })
+        .rpc();
+      await setSolSpendLimit(SPEND_LIMIT);
+      // The recipient must already be rent exempt to receive a small payout
+      await airdrop(recipient.publicKey, 1);
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: wallet.publicKey,
+            toPubkey: pda("treasury"),
+            lamports: anchor.web3.LAMPORTS_PER_SOL,
+          })
+        )
+      );
+
+      // Each spend fits the limit on its own, but not both in one period
+      const spend = {
+        treasurySpend: {
+          recipient: recipient.publicKey,
+          amount: new anchor.BN(SPEND_AMOUNT),
+          mint: null,
+        },
+      };
+      first = await createProposal({ treasurySpend: {} }, spend);
+      second = await createProposal({ treasurySpend: {} }, spend);
+      await vote(first);
+      await vote(second);
+      await waitForVotingEnd(second);
+      for (const proposal of [first, second]) {
+        await finalize(proposal);
+        await queue(proposal);
+      }
+    });
+
+    it("Pays out approved spends within the period limit", async () => {
+      const balanceBefore = await provider.connection.getBalance(recipient.publicKey);
+      await executeSpend(first);
+
+      const balanceAfter = await provider.connection.getBalance(recipient.publicKey);
+      expect(balanceAfter - balanceBefore).to.equal(SPEND_AMOUNT);
+    });
+
+    it("Rejects spends beyond the period limit", async () => {
+      await expectError(executeSpend(second), "TreasuryLimitExceeded");
+    });
+  });
+
+  describe("commit-reveal", () => {
+    const node = Keypair.generate();
+    const result = "QmSealedResult";
+    const salt = Buffer.alloc(32, 7);
+    let taskId: anchor.BN;
+    const commitOf = () =>
+      pda("reasoning-commit", u64(taskId), Buffer.from([0]), node.publicKey.toBuffer());
+
+    before(async () => {
+      await registerNode(node);
+      taskId = await submitTask({ consensusGuarded: {} });
+      await claim(taskId, node);
+    });
+
+    it("Escrows a reveal bond with each sealed result", async () => {
+      const commitment = createHash("sha256")
+        .update(Buffer.from(result))
+        .update(salt)
+        .update(node.publicKey.toBuffer())
+        .digest();
+      await program.methods
+        .commitReasoning(taskId, Array.from(commitment))
+        .accountsPartial({
+          nodeOwner: node.publicKey,
+          reasoningNode: nodeOf(node.publicKey),
+          task: taskPda(taskId),
+          commit: commitOf(),
+          programState,
+        })
+        .signers([node])
+        .rpc();
+
+      const commit = await program.account.reasoningCommit.fetch(commitOf());
+      expect(Buffer.from(commit.commitment)).to.deep.equal(commitment);
+      expect(commit.bond.toNumber()).to.equal(10_000_000);
+      const task = await program.account.troTask.fetch(taskPda(taskId));
+      expect(task.commitCount).to.equal(1);
+    });
+
+    it("Keeps results sealed until the commit window closes", async () => {
+      await expectError(
+        program.methods
+          .revealReasoning(taskId, result, Array.from(salt), "metadata", "QmCid", 9000, false)
+          .accountsPartial({
+            nodeOwner: node.publicKey,
+            reasoningNode: nodeOf(node.publicKey),
+            task: taskPda(taskId),
+            commit: commitOf(),
+            inferenceResult: pda(
+              "inference-result",
+              u64(taskId),
+              node.publicKey.toBuffer(),
+              Buffer.from([0])
+            ),
+            programState,
+          })
+          .signers([node])
+          .rpc(),
+        "RevealWindowNotOpen"
+      );
+    });
   });
-});
 
+  describe("multi-node assignment", () => {
+    const nodes = [Keypair.generate(), Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    let taskId: anchor.BN;
+
+    before(async () => {
+      for (const node of nodes) {
+        await registerNode(node);
+      }
+      taskId = await submitTask({ consensusGuarded: {} });
+    });
+
+    it("Assigns ConsensusGuarded tasks to the required number of nodes", async () => {
+      for (const node of nodes.slice(0, 3)) {
+        await claim(taskId, node);
+      }
+
+      const task = await program.account.troTask.fetch(taskPda(taskId));
+      expect(task.requiredNodes).to.equal(3);
+      expect(task.assignedNodes.map((owner) => owner.toString())).to.have.members(
+        nodes.slice(0, 3).map((node) => node.publicKey.toString())
+      );
+      expect(task.status).to.have.property("reasoning");
+    });
+
+    it("Refuses claims once every seat is taken", async () => {
+      await expectError(claim(taskId, nodes[3]), "TaskFullyAssigned");
+    });
+  });
+
+  describe("stalled task reclaim", () => {
+    const node = Keypair.generate();
+    let taskId: anchor.BN;
+
+    before(async () => {
+      await registerNode(node);
+      taskId = await submitTask({ balanced: {} });
+      await claim(taskId, node);
+    });
+
+    it("Leaves claims alone until the task timeout passes", async () => {
+      await expectError(reclaim(taskId, node.publicKey), "ClaimNotExpired");
+    });
+
+    it("Returns expired claims to the pool", async () => {
+      await waitForClaimTimeout(node.publicKey);
+      await reclaim(taskId, node.publicKey);
+
+      const task = await program.account.troTask.fetch(taskPda(taskId));
+      expect(task.status).to.have.property("pending");
+      expect(task.assignedNodes).to.have.length(0);
+      const nodeAccount = await program.account.reasoningNode.fetch(nodeOf(node.publicKey));
+      expect(nodeAccount.activeTaskId.toNumber()).to.equal(0);
+      // Within the offline grace period, so no stake is slashed
+      expect(nodeAccount.stakeAmount.toNumber()).to.equal(anchor.web3.LAMPORTS_PER_SOL / 10);
+    });
+  });
+
+  describe("task release", () => {
+    const skipped = Keypair.generate();
+    const finished = Keypair.generate();
+
+    before(async () => {
+      await registerNode(skipped);
+      await registerNode(finished);
+    });
+
+    it("Keeps a node with an open claim from claiming another task", async () => {
+      const firstTask = await submitTask({ balanced: {} });
+      const secondTask = await submitTask({ balanced: {} });
+      await claim(firstTask, skipped);
+
+      await expectError(claim(secondTask, skipped), "NodeBusy");
+    });
+
+    it("Lets a skipped node claim a second task once reclaimed", async () => {
+      const nodeAccount = await program.account.reasoningNode.fetch(nodeOf(skipped.publicKey));
+      const stalledTask = nodeAccount.activeTaskId;
+      await waitForClaimTimeout(skipped.publicKey);
+      await reclaim(stalledTask, skipped.publicKey);
+
+      const nextTask = await submitTask({ balanced: {} });
+      await claim(nextTask, skipped);
+
+      const released = await program.account.reasoningNode.fetch(nodeOf(skipped.publicKey));
+      expect(released.activeTaskId.toString()).to.equal(nextTask.toString());
+    });
+
+    it("Releases a node once its reasoning is submitted", async () => {
+      const firstTask = await submitTask({ balanced: {} });
+      await claim(firstTask, finished);
+      await program.methods
+        .submitReasoning(firstTask, "QmResult", "metadata", "QmCid", 9000, false)
+        .accountsPartial({
+          nodeOwner: finished.publicKey,
+          reasoningNode: nodeOf(finished.publicKey),
+          task: taskPda(firstTask),
+          inferenceResult: pda(
+            "inference-result",
+            u64(firstTask),
+            finished.publicKey.toBuffer(),
+            Buffer.from([0])
+          ),
+          programState,
+        })
+        .signers([finished])
+        .rpc();
+
+      const nextTask = await submitTask({ balanced: {} });
+      await claim(nextTask, finished);
+
+      const nodeAccount = await program.account.reasoningNode.fetch(nodeOf(finished.publicKey));
+      expect(nodeAccount.activeTaskId.toString()).to.equal(nextTask.toString());
+    });
+  });
+
+  describe("proposal analysis tasks", () => {
+    const analyst = Keypair.generate();
+    const STAKE_POOL = anchor.web3.LAMPORTS_PER_SOL / 100;
+    let proposalId: string;
+    let proposal: PublicKey;
+    let taskId: anchor.BN;
+
+    before(async () => {
+      await registerNode(analyst);
+      ({ proposalId, proposal, taskId } = await submitAnalyzedProposal(STAKE_POOL));
+    });
+
+    it("Opens a governance task linked both ways and funds its stake pool", async () => {
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      expect(proposalAccount.analysisTask!.equals(taskPda(taskId))).to.be.true;
+      const task = await program.account.troTask.fetch(taskPda(taskId));
+      expect(task.taskType).to.have.property("governance");
+      expect(task.proposal!.equals(proposal)).to.be.true;
+      expect(task.stakePool.toNumber()).to.equal(STAKE_POOL);
+      expect(task.intent).to.equal(`governance-proposal:${proposalId}`);
+    });
+
+    it("Requires the linked proposal when the task is claimed", async () => {
+      await expectError(claim(taskId, analyst), "InvalidProposalLink");
+    });
+
+    it("Moves the proposal to Analyzing once a node claims the task", async () => {
+      await claim(taskId, analyst, proposal);
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      expect(proposalAccount.status).to.have.property("analyzing");
+    });
+
+    it("Lets only the submitter cancel an unclaimed task and refunds its stake pool", async () => {
+      const unclaimed = await submitAnalyzedProposal(STAKE_POOL);
+      const cancel = (signer: Keypair) =>
+        program.methods
+          .cancelProposalAnalysis(unclaimed.proposalId)
+          .accountsPartial({
+            submitter: signer.publicKey,
+            proposal: unclaimed.proposal,
+            analysisTask: taskPda(unclaimed.taskId),
+          })
+          .signers([signer])
+          .rpc();
+      await expectError(cancel(analyst), "ConstraintHasOne");
+
+      const before = await provider.connection.getBalance(submitter.publicKey);
+      await cancel(submitter);
+
+      const after = await provider.connection.getBalance(submitter.publicKey);
+      expect(after - before).to.equal(STAKE_POOL);
+      const task = await program.account.troTask.fetch(taskPda(unclaimed.taskId));
+      expect(task.status).to.have.property("cancelled");
+      expect(task.stakePool.toNumber()).to.equal(0);
+      const proposalAccount = await program.account.proposal.fetch(unclaimed.proposal);
+      expect(proposalAccount.analysisTask).to.be.null;
+    });
+
+    it("Takes the proposal back to Submitted when a stalled claim is reclaimed", async () => {
+      const stalled = Keypair.generate();
+      await registerNode(stalled);
+      const reopened = await submitAnalyzedProposal(STAKE_POOL);
+      await claim(reopened.taskId, stalled, reopened.proposal);
+      await waitForClaimTimeout(stalled.publicKey);
+
+      await reclaim(reopened.taskId, stalled.publicKey);
+
+      const task = await program.account.troTask.fetch(taskPda(reopened.taskId));
+      expect(task.status).to.have.property("pending");
+      const proposalAccount = await program.account.proposal.fetch(reopened.proposal);
+      expect(proposalAccount.status).to.have.property("submitted");
+    });
+
+    it("Refuses to cancel once a node has claimed the task", async () => {
+      await expectError(
+        program.methods
+          .cancelProposalAnalysis(proposalId)
+          .accountsPartial({ submitter: submitter.publicKey, proposal, analysisTask: taskPda(taskId) })
+          .signers([submitter])
+          .rpc(),
+        "TaskNotClaimable"
+      );
+    });
+  });
+
+  describe("proposal amendments", () => {
+    const analyst = Keypair.generate();
+    let proposalId: string;
+    let proposal: PublicKey;
+    let taskId: anchor.BN;
+
+    function amend(ipfsHash: string, signer: Keypair = submitter) {
+      return program.methods
+        .amendProposal(proposalId, ipfsHash)
+        .accountsPartial({ submitter: signer.publicKey, proposal, analysisTask: taskPda(taskId) })
+        .signers([signer])
+        .rpc();
+    }
+
+    before(async () => {
+      await registerNode(analyst);
+      ({ proposalId, proposal, taskId } = await submitAnalyzedProposal(0));
+    });
+
+    it("Only lets the submitter amend", async () => {
+      await expectError(amend("QmAmendedByOutsider", analyst), "ConstraintHasOne");
+    });
+
+    it("Appends a revision and points the pending task at the new content", async () => {
+      await amend("QmAmendedProposal");
+
+      const proposalAccount = await program.account.proposal.fetch(proposal);
+      expect(proposalAccount.ipfsHash).to.equal("QmAmendedProposal");
+      expect(proposalAccount.revisions).to.have.length(2);
+      expect(proposalAccount.revisions[0].ipfsHash).to.equal("QmAnalyzedProposal");
+      expect(proposalAccount.revisions[1].revision).to.equal(1);
+      const task = await program.account.troTask.fetch(taskPda(taskId));
+      expect(task.metadataHash).to.equal("QmAmendedProposal");
+    });
+
+    it("Refuses amendments once analysis has started", async () => {
+      await claim(taskId, analyst, proposal);
+
+      await expectError(amend("QmTooLate"), "ProposalNotAmendable");
+    });
+  });
+
+  describe("legacy node migration", () => {
+    // Owns the old-layout node preloaded from tests/fixtures/legacy-node.json:
+    // reputation 80/100, 42 inferences, active
+    const legacyOwner = Keypair.fromSeed(Uint8Array.from(Array(32).fill(7)));
+    const legacyNode = pda("node", legacyOwner.publicKey.toBuffer());
+
+    function migrate(owner: Keypair) {
+      return program.methods
+        .migrateLegacyNode({ local7B: {} }, { balanced: {} })
+        .accountsPartial({
+          owner: owner.publicKey,
+          legacyNode: pda("node", owner.publicKey.toBuffer()),
+          reasoningNode: nodeOf(owner.publicKey),
+          programState,
+        })
+        .signers([owner])
+        .rpc();
+    }
+
+    before(async () => {
+      await airdrop(legacyOwner.publicKey);
+    });
+
+    it("Rejects owners without a legacy node", async () => {
+      const outsider = Keypair.generate();
+      await airdrop(outsider.publicKey);
+
+      await expectError(migrate(outsider), "InvalidLegacyNode");
+    });
+
+    it("Carries reputation and inferences over and closes the legacy account", async () => {
+      await migrate(legacyOwner);
+
+      const node = await program.account.reasoningNode.fetch(nodeOf(legacyOwner.publicKey));
+      expect(node.reputationScoreBps).to.equal(8_000);
+      expect(node.totalInferences.toNumber()).to.equal(42);
+      expect(node.stakeAmount.toNumber()).to.equal(0);
+      expect(node.status).to.have.property("active");
+      expect(await provider.connection.getAccountInfo(legacyNode)).to.be.null;
+    });
+
+    it("Migrates inactive legacy nodes as active so they can stake", async () => {
+      // Owns tests/fixtures/inactive-legacy-node.json: reputation 30/100, 5 inferences, inactive
+      const inactiveOwner = Keypair.fromSeed(Uint8Array.from(Array(32).fill(10)));
+      await airdrop(inactiveOwner.publicKey);
+      await migrate(inactiveOwner);
+
+      await program.methods
+        .depositStake(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10))
+        .accountsPartial({
+          nodeOwner: inactiveOwner.publicKey,
+          reasoningNode: nodeOf(inactiveOwner.publicKey),
+          stakeVault: pda("stake-vault", inactiveOwner.publicKey.toBuffer()),
+          programState,
+        })
+        .signers([inactiveOwner])
+        .rpc();
+
+      const node = await program.account.reasoningNode.fetch(nodeOf(inactiveOwner.publicKey));
+      expect(node.status).to.have.property("active");
+      expect(node.reputationScoreBps).to.equal(3_000);
+      expect(node.stakeAmount.toNumber()).to.equal(anchor.web3.LAMPORTS_PER_SOL / 10);
+    });
+  });
+
+  describe("submitter ratings", () => {
+    // tests/fixtures/finalized-task.json preloads a finalized task submitted by
+    // taskSubmitter and answered by resultOwner's node, not yet rated
+    const FINALIZED_TASK_ID = new anchor.BN(900_000_001);
+    const taskSubmitter = Keypair.fromSeed(Uint8Array.from(Array(32).fill(9)));
+    const resultOwner = Keypair.fromSeed(Uint8Array.from(Array(32).fill(8)));
+
+    function rate(rater: Keypair, taskId: anchor.BN, score: number) {
+      return program.methods
+        .rateNode(resultOwner.publicKey, taskId, score)
+        .accountsPartial({
+          rater: rater.publicKey,
+          node: nodeOf(resultOwner.publicKey),
+          task: taskPda(taskId),
+        })
+        .signers([rater])
+        .rpc();
+    }
+
+    before(async () => {
+      await registerNode(resultOwner);
+      await airdrop(taskSubmitter.publicKey);
+    });
+
+    it("Rejects ratings from anyone but the task submitter", async () => {
+      const outsider = Keypair.generate();
+      await airdrop(outsider.publicKey);
+      await expectError(rate(outsider, FINALIZED_TASK_ID, 100), "RatingNotAllowed");
+    });
+
+    it("Rejects ratings before the task is finalized", async () => {
+      const taskId = await submitTask({ balanced: {} });
+      await claim(taskId, resultOwner);
+      await program.methods
+        .submitReasoning(taskId, "QmResult", "metadata", "QmCid", 9000, false)
+        .accountsPartial({
+          nodeOwner: resultOwner.publicKey,
+          reasoningNode: nodeOf(resultOwner.publicKey),
+          task: taskPda(taskId),
+          inferenceResult: pda(
+            "inference-result",
+            u64(taskId),
+            resultOwner.publicKey.toBuffer(),
+            Buffer.from([0])
+          ),
+          programState,
+        })
+        .signers([resultOwner])
+        .rpc();
+
+      await expectError(rate(wallet.payer, taskId, 100), "RatingNotAllowed");
+    });
+
+    it("Lets the submitter rate the result node exactly once", async () => {
+      await rate(taskSubmitter, FINALIZED_TASK_ID, 100);
+
+      const node = await program.account.reasoningNode.fetch(nodeOf(resultOwner.publicKey));
+      expect(node.reputationScoreBps).to.equal(6_800);
+      const task = await program.account.troTask.fetch(taskPda(FINALIZED_TASK_ID));
+      expect(task.rated).to.be.true;
+
+      await expectError(rate(taskSubmitter, FINALIZED_TASK_ID, 100), "AlreadyRated");
+    });
+  });
+
+  describe("unbonding", () => {
+    const node = Keypair.generate();
+    const STAKE = anchor.web3.LAMPORTS_PER_SOL / 10;
+
+    before(async () => {
+      await registerNode(node, STAKE);
+    });
+
+    it("Moves stake into unbonding", async () => {
+      await program.methods
+        .requestUnbond(new anchor.BN(STAKE / 2))
+        .accountsPartial({
+          nodeOwner: node.publicKey,
+          reasoningNode: nodeOf(node.publicKey),
+          programState,
+        })
+        .signers([node])
+        .rpc();
+
+      const nodeAccount = await program.account.reasoningNode.fetch(nodeOf(node.publicKey));
+      expect(nodeAccount.stakeAmount.toNumber()).to.equal(STAKE / 2);
+      expect(nodeAccount.unbondingAmount.toNumber()).to.equal(STAKE / 2);
+      expect(nodeAccount.unbondAvailableTs.toNumber()).to.be.greaterThan(await clusterTime());
+    });
+
+    it("Holds unbonding stake in the vault through the cooldown", async () => {
+      const stakeVault = pda("stake-vault", node.publicKey.toBuffer());
+      await expectError(
+        program.methods
+          .completeUnbond()
+          .accountsPartial({
+            nodeOwner: node.publicKey,
+            reasoningNode: nodeOf(node.publicKey),
+            stakeVault,
+            programState,
+          })
+          .signers([node])
+          .rpc(),
+        "StakeLocked"
+      );
+
+      const vault = await program.account.stakeVault.fetch(stakeVault);
+      expect(vault.totalStake.toNumber()).to.equal(STAKE);
+    });
+
+    it("Retires the node once all stake is unbonding and refuses new stake", async () => {
+      const requestUnbond = (amount: number) =>
+        program.methods
+          .requestUnbond(new anchor.BN(amount))
+          .accountsPartial({
+            nodeOwner: node.publicKey,
+            reasoningNode: nodeOf(node.publicKey),
+            programState,
+          })
+          .signers([node])
+          .rpc();
+      await requestUnbond(STAKE / 2);
+
+      const nodeAccount = await program.account.reasoningNode.fetch(nodeOf(node.publicKey));
+      expect(nodeAccount.status).to.have.property("retired");
+      await expectError(requestUnbond(1), "NodeInactive");
+      await expectError(
+        program.methods
+          .depositStake(new anchor.BN(STAKE))
+          .accountsPartial({
+            nodeOwner: node.publicKey,
+            reasoningNode: nodeOf(node.publicKey),
+            stakeVault: pda("stake-vault", node.publicKey.toBuffer()),
+            programState,
+          })
+          .signers([node])
+          .rpc(),
+        "NodeInactive"
+      );
+    });
+  });
+});